@@ -14,8 +14,13 @@ use crate::{
 use color_eyre::eyre::Report;
 use uuid::Uuid;
 
+pub mod cache;
 pub mod extra_types;
 pub mod fmt;
+pub mod metrics;
+pub mod poll;
+pub mod results;
+pub mod schema;
 
 pub const NEW_TOKEN_URL: &str = "https://aerocloud.nablaflow.io/developer/api";
 
@@ -0,0 +1,434 @@
+use crate::{
+    aerocloud::types::{
+        Id, ListPageNav, ModelV7, PaginationOffset, ProjectV7, SimulationV7,
+    },
+    args::Args,
+};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{self, WrapErr};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+use tokio::fs;
+use tracing::debug;
+use uuid::Uuid;
+
+/// A cached listing as stored on disk.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Entry<T> {
+    pub fetched_at: DateTime<Utc>,
+    pub items: Vec<T>,
+}
+
+/// Summary of a cached listing, as shown by `nf cache stats`.
+#[derive(Serialize, Debug)]
+pub struct EntryStats {
+    pub name: String,
+    pub items: usize,
+    pub bytes: u64,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Local cache of the last fetched projects, simulations and models, one JSON
+/// file per listing.
+#[derive(Clone, Debug)]
+pub struct Cache {
+    dir: PathBuf,
+    offline: bool,
+}
+
+impl Cache {
+    pub fn default_dir() -> Option<PathBuf> {
+        Some(dirs::cache_dir()?.join("nablaflow").join("aerocloud"))
+    }
+
+    pub fn new(args: &Args) -> Self {
+        Self {
+            dir: args.cache_dir.clone(),
+            offline: args.offline,
+        }
+    }
+
+    #[must_use]
+    pub fn with_offline(self, offline: bool) -> Self {
+        Self { offline, ..self }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub async fn projects<F, Fut>(
+        &self,
+        fetch_page: F,
+    ) -> eyre::Result<Vec<ProjectV7>>
+    where
+        F: FnMut(PaginationOffset) -> Fut,
+        Fut: Future<Output = eyre::Result<(Vec<ProjectV7>, ListPageNav)>>,
+    {
+        self.list("projects", fetch_page).await
+    }
+
+    pub async fn reusable_models<F, Fut>(
+        &self,
+        fetch_page: F,
+    ) -> eyre::Result<Vec<ModelV7>>
+    where
+        F: FnMut(PaginationOffset) -> Fut,
+        Fut: Future<Output = eyre::Result<(Vec<ModelV7>, ListPageNav)>>,
+    {
+        self.list("reusable-models", fetch_page).await
+    }
+
+    /// Simulations of a project, cached separately for each `query` the API
+    /// filters them by, empty when unfiltered.
+    pub async fn simulations<F, Fut>(
+        &self,
+        project_id: &Id,
+        query: &str,
+        fetch_page: F,
+    ) -> eyre::Result<Vec<SimulationV7>>
+    where
+        F: FnMut(PaginationOffset) -> Fut,
+        Fut: Future<Output = eyre::Result<(Vec<SimulationV7>, ListPageNav)>>,
+    {
+        let name = if query.is_empty() {
+            format!("simulations-{project_id}")
+        } else {
+            format!("simulations-{project_id}-{query}")
+        };
+
+        self.list(&name, fetch_page).await
+    }
+
+    /// Simulations of all cached projects.
+    pub async fn all_simulations(&self) -> eyre::Result<Vec<SimulationV7>> {
+        let mut sims = vec![];
+        let mut seen = HashSet::new();
+
+        for (name, _) in self.entries().await? {
+            if name.starts_with("simulations-")
+                && let Some(entry) = self.load::<SimulationV7>(&name).await?
+            {
+                // NOTE: listings filtered by different queries overlap.
+                sims.extend(
+                    entry.items.into_iter().filter(|sim| seen.insert(sim.id.0)),
+                );
+            }
+        }
+
+        Ok(sims)
+    }
+
+    /// Removes deleted objects from all cached listings, along with the
+    /// simulations of deleted projects.
+    pub async fn evict(&self, ids: &[Id]) -> eyre::Result<()> {
+        let ids: HashSet<Uuid> = ids.iter().map(|id| id.0).collect();
+
+        for (name, path) in self.entries().await? {
+            if ids
+                .iter()
+                .any(|id| name.starts_with(&format!("simulations-{id}")))
+            {
+                fs::remove_file(&path)
+                    .await
+                    .wrap_err_with(|| format!("removing {}", path.display()))?;
+                continue;
+            }
+
+            let Some(mut entry) = self.load::<serde_json::Value>(&name).await?
+            else {
+                continue;
+            };
+
+            let len = entry.items.len();
+
+            entry.items.retain(|item| {
+                item.get("id")
+                    .and_then(serde_json::Value::as_str)
+                    .and_then(|id| Uuid::parse_str(id).ok())
+                    .is_none_or(|id| !ids.contains(&id))
+            });
+
+            if entry.items.len() != len {
+                self.save(&name, &entry).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fetches a single project, or looks it up in cached projects when offline.
+    pub async fn project<Fut>(
+        &self,
+        id: &Id,
+        fetch: Fut,
+    ) -> eyre::Result<ProjectV7>
+    where
+        Fut: Future<Output = eyre::Result<ProjectV7>>,
+    {
+        if !self.offline {
+            return fetch.await;
+        }
+
+        self.load::<ProjectV7>("projects")
+            .await?
+            .and_then(|entry| entry.items.into_iter().find(|p| p.id.0 == id.0))
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "project `{id}` is not cached, run `nf aerocloud v7 list-projects` without `--offline` first"
+                )
+            })
+    }
+
+    /// Lists all items, fetching every page and storing them for `--offline`.
+    /// When offline, only the cache is read.
+    ///
+    /// NOTE: listings are not refreshed incrementally: the API neither documents
+    /// an order for its pages nor filters by `updated_at`, so an unchanged page
+    /// says nothing about the following ones.
+    async fn list<T, F, Fut>(
+        &self,
+        name: &str,
+        mut fetch_page: F,
+    ) -> eyre::Result<Vec<T>>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnMut(PaginationOffset) -> Fut,
+        Fut: Future<Output = eyre::Result<(Vec<T>, ListPageNav)>>,
+    {
+        if self.offline {
+            return self
+                .load::<T>(name)
+                .await?
+                .map(|entry| entry.items)
+                .ok_or_else(|| {
+                    eyre::eyre!(
+                        "`{name}` is not cached yet, run the same command without `--offline` first"
+                    )
+                });
+        }
+
+        let mut items: Vec<T> = vec![];
+        let mut offset = PaginationOffset(0u64);
+
+        loop {
+            let (page, nav) = fetch_page(offset).await?;
+            items.extend(page);
+
+            let Some(next_offset) = nav.next_offset else {
+                break;
+            };
+
+            offset = PaginationOffset(next_offset);
+        }
+
+        let entry = Entry {
+            fetched_at: Utc::now(),
+            items,
+        };
+
+        self.save(name, &entry).await?;
+
+        Ok(entry.items)
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+
+    async fn load<T: DeserializeOwned>(
+        &self,
+        name: &str,
+    ) -> eyre::Result<Option<Entry<T>>> {
+        let path = self.path(name);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let buf = fs::read(&path)
+            .await
+            .wrap_err_with(|| format!("reading {}", path.display()))?;
+
+        match serde_json::from_slice(&buf) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(err) if !self.offline => {
+                debug!("ignoring unreadable cache `{}`: {err}", path.display());
+                Ok(None)
+            }
+            Err(err) => Err(err)
+                .wrap_err_with(|| format!("parsing {} as json", path.display())),
+        }
+    }
+
+    async fn save<T: Serialize>(
+        &self,
+        name: &str,
+        entry: &Entry<T>,
+    ) -> eyre::Result<()> {
+        let buf =
+            serde_json::to_vec(entry).wrap_err("serializing cache to json")?;
+
+        fs::create_dir_all(&self.dir)
+            .await
+            .wrap_err("creating cache folder")?;
+
+        let path = self.path(name);
+        let tmp_path = path.with_extension("json.tmp");
+
+        fs::write(&tmp_path, &buf)
+            .await
+            .wrap_err_with(|| format!("writing {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .await
+            .wrap_err_with(|| format!("writing {}", path.display()))
+    }
+
+    pub async fn stats(&self) -> eyre::Result<Vec<EntryStats>> {
+        #[derive(Deserialize)]
+        struct Partial {
+            fetched_at: DateTime<Utc>,
+            items: Vec<serde::de::IgnoredAny>,
+        }
+
+        let mut stats = vec![];
+
+        for (name, path) in self.entries().await? {
+            let buf = fs::read(&path)
+                .await
+                .wrap_err_with(|| format!("reading {}", path.display()))?;
+
+            let Ok(partial) = serde_json::from_slice::<Partial>(&buf) else {
+                continue;
+            };
+
+            stats.push(EntryStats {
+                name,
+                items: partial.items.len(),
+                bytes: buf.len() as u64,
+                fetched_at: partial.fetched_at,
+            });
+        }
+
+        Ok(stats)
+    }
+
+    /// Removes all cached listings, returning how many were removed.
+    pub async fn clear(&self) -> eyre::Result<usize> {
+        let entries = self.entries().await?;
+
+        for (_, path) in &entries {
+            fs::remove_file(path)
+                .await
+                .wrap_err_with(|| format!("removing {}", path.display()))?;
+        }
+
+        Ok(entries.len())
+    }
+
+    /// Names and paths of cached listings, sorted by name.
+    async fn entries(&self) -> eyre::Result<Vec<(String, PathBuf)>> {
+        let mut entries = vec![];
+
+        if !self.dir.exists() {
+            return Ok(entries);
+        }
+
+        let mut read_dir = fs::read_dir(&self.dir)
+            .await
+            .wrap_err_with(|| format!("reading {}", self.dir.display()))?;
+
+        while let Some(dir_entry) = read_dir.next_entry().await? {
+            let path = dir_entry.path();
+
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            if let Some(stem) = path.file_stem() {
+                entries.push((stem.to_string_lossy().into_owned(), path));
+            }
+        }
+
+        entries.sort();
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn page(
+        items: &[Value],
+        offset: u64,
+        total_count: u64,
+    ) -> (Vec<Value>, ListPageNav) {
+        let next_offset = offset + items.len() as u64;
+
+        let nav = ListPageNav {
+            current_offset: offset,
+            current_page: offset,
+            limit: 1,
+            next_offset: (next_offset < total_count).then_some(next_offset),
+            next_page: None,
+            previous_offset: None,
+            previous_page: None,
+            total_count,
+            total_pages: total_count,
+        };
+
+        (items.to_vec(), nav)
+    }
+
+    async fn list(cache: &Cache, listing: &[Value]) -> Vec<Value> {
+        cache
+            .list("simulations-test", |offset| async move {
+                #[allow(clippy::cast_possible_truncation)]
+                let item = &listing[*offset as usize];
+                Ok(page(
+                    std::slice::from_ref(item),
+                    *offset,
+                    listing.len() as u64,
+                ))
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn refreshes_items_changed_after_an_unchanged_page() {
+        let cache = Cache {
+            dir: std::env::temp_dir()
+                .join(format!("nf-cache-{}", Uuid::new_v4())),
+            offline: false,
+        };
+
+        // NOTE: pages are not sorted by `updated_at`, the most recently updated
+        // simulation comes last.
+        let a = json!({"id": Uuid::new_v4(), "name": "a", "status": "success", "updated_at": "2026-10-02T00:00:00Z"});
+        let b = json!({"id": Uuid::new_v4(), "name": "b", "status": "success", "updated_at": "2026-10-01T00:00:00Z"});
+
+        assert_eq!(
+            list(&cache, &[a.clone(), b.clone()]).await,
+            [a.clone(), b.clone()]
+        );
+
+        let mut renamed = b;
+        renamed["name"] = "renamed".into();
+        renamed["updated_at"] = "2026-10-03T00:00:00Z".into();
+
+        let listing = [a, renamed];
+        assert_eq!(list(&cache, &listing).await, listing);
+
+        let offline = cache.clone().with_offline(true);
+        assert_eq!(list(&offline, &[]).await, listing);
+
+        fs::remove_dir_all(&cache.dir).await.unwrap();
+    }
+}
@@ -1,4 +1,11 @@
-use crate::aerocloud::types::{BoundaryLayerTreatment, SimulationStatus};
+use crate::{
+    aerocloud::types::{
+        BoundaryLayerTreatment, Ceiling, CeilingCondition, SimulationParamsV7,
+        SimulationStatus,
+    },
+    fmt::NOT_AVAILABLE,
+};
+use std::fmt::Write as _;
 
 pub fn human_simulation_status(v: SimulationStatus) -> &'static str {
     match v {
@@ -17,3 +24,39 @@ pub fn human_boundary_layer_treatment(v: BoundaryLayerTreatment) -> &'static str
         }
     }
 }
+
+pub fn human_boundary(params: &SimulationParamsV7) -> String {
+    if params.has_ground {
+        let mut s = format!(
+            "ground, {}",
+            if params.is_ground_moving {
+                "moving"
+            } else {
+                "still"
+            },
+        );
+
+        if params.ground_offset.0 != 0.0 {
+            let _ = write!(s, ", offset: {:.2} m", params.ground_offset);
+        }
+
+        s
+    } else if let Some(Ceiling { offset, condition }) = &params.ceiling {
+        let mut s = format!(
+            "ceiling, {}",
+            if *condition == CeilingCondition::Moving {
+                "moving"
+            } else {
+                "still"
+            },
+        );
+
+        if offset.0 != 0.0 {
+            let _ = write!(s, ", offset: {:.2} m", offset.0);
+        }
+
+        s
+    } else {
+        NOT_AVAILABLE.into()
+    }
+}
@@ -0,0 +1,168 @@
+use crate::aerocloud::{
+    results::{Quantity, UnitSystem},
+    types::{Id, SimulationResultsV7YawAnglesItem, SimulationV7},
+};
+
+/// A quantity reported for every yaw angle in the results of a simulation.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Cd,
+    Cl,
+    Cs,
+    Cda,
+    Cla,
+    Csa,
+    Fd,
+    Fl,
+    Fs,
+    Mr,
+    My,
+    Mp,
+    SurfaceArea,
+    HeatTransfer,
+    HeatTransferCoefficient,
+}
+
+impl Metric {
+    pub fn value(self, res: &SimulationResultsV7YawAnglesItem) -> f64 {
+        match self {
+            Self::Cd => res.cd,
+            Self::Cl => res.cl,
+            Self::Cs => res.cs,
+            Self::Cda => res.cda,
+            Self::Cla => res.cla,
+            Self::Csa => res.csa,
+            Self::Fd => res.fd,
+            Self::Fl => res.fl,
+            Self::Fs => res.fs,
+            Self::Mr => res.mr,
+            Self::My => res.my,
+            Self::Mp => res.mp,
+            Self::SurfaceArea => res.surface_area,
+            Self::HeatTransfer => res.heat_transfer,
+            Self::HeatTransferCoefficient => res.heat_transfer_coefficient,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Cd => "Cd",
+            Self::Cl => "Cl",
+            Self::Cs => "Cs",
+            Self::Cda => "Cda",
+            Self::Cla => "Cla",
+            Self::Csa => "Csa",
+            Self::Fd => "Fd",
+            Self::Fl => "Fl",
+            Self::Fs => "Fs",
+            Self::Mr => "Mr",
+            Self::My => "My",
+            Self::Mp => "Mp",
+            Self::SurfaceArea => "Surface",
+            Self::HeatTransfer => "Heat transfer",
+            Self::HeatTransferCoefficient => "Heat transfer coeff",
+        }
+    }
+
+    pub fn quantity(self) -> Option<Quantity> {
+        match self {
+            Self::Cd | Self::Cl | Self::Cs => None,
+            Self::Cda | Self::Cla | Self::Csa | Self::SurfaceArea => {
+                Some(Quantity::Area)
+            }
+            Self::Fd | Self::Fl | Self::Fs => Some(Quantity::Force),
+            Self::Mr | Self::My | Self::Mp => Some(Quantity::Moment),
+            Self::HeatTransfer => Some(Quantity::HeatTransfer),
+            Self::HeatTransferCoefficient => {
+                Some(Quantity::HeatTransferCoefficient)
+            }
+        }
+    }
+
+    /// Like [`Metric::value`], converted to `units`.
+    pub fn value_in(
+        self,
+        res: &SimulationResultsV7YawAnglesItem,
+        units: UnitSystem,
+    ) -> f64 {
+        let value = self.value(res);
+
+        match self.quantity() {
+            Some(quantity) => units.convert(quantity, value).0,
+            None => value,
+        }
+    }
+
+    pub fn unit(self, units: UnitSystem) -> Option<&'static str> {
+        self.quantity().map(|quantity| units.unit(quantity))
+    }
+
+    pub fn title(self, units: UnitSystem) -> String {
+        match self.unit(units) {
+            Some(unit) => format!("{} [{unit}]", self.label()),
+            None => self.label().to_owned(),
+        }
+    }
+}
+
+/// Returns the range spanned by `values`, padded so that points do not lie on the
+/// chart borders.
+pub fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+
+    let pad = if (max - min).abs() < f64::EPSILON {
+        min.abs().max(1.0) * 0.1
+    } else {
+        (max - min) * 0.05
+    };
+
+    (min - pad, max + pad)
+}
+
+/// The values of a metric for each yaw angle of a simulation, sorted by yaw angle.
+#[derive(Debug, serde::Serialize)]
+pub struct Series {
+    pub id: Id,
+    pub name: String,
+    pub revision: Option<String>,
+    pub points: Vec<(f64, f64)>,
+}
+
+impl Series {
+    /// `None` when the simulation has no results, or results with no yaw angles.
+    pub fn from_simulation(
+        sim: &SimulationV7,
+        metric: Metric,
+        units: UnitSystem,
+    ) -> Option<Self> {
+        let results = sim.results.as_ref()?;
+
+        let mut points: Vec<(f64, f64)> = results
+            .yaw_angles
+            .iter()
+            .map(|res| (res.yaw_angle.0, metric.value_in(res, units)))
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        if points.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            id: sim.id.clone(),
+            name: sim.name.clone(),
+            revision: sim.revision.clone(),
+            points,
+        })
+    }
+
+    pub fn legend(&self) -> String {
+        match &self.revision {
+            Some(revision) => format!("{} ({revision})", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::aerocloud::{
+    Client, fmt_progenitor_err,
+    types::{
+        Id, ListPageSimulationsV7, PaginationOffset, SimulationV7,
+        SimulationsV7ListStatus,
+    },
+};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{self, WrapErr};
+use progenitor_client::ResponseValue;
+use reqwest::StatusCode;
+use std::fmt;
+use tokio::task::JoinSet;
+use tracing::debug;
+
+/// Selects simulations of a project, to track those submitted after polling started.
+#[derive(Clone, Debug)]
+pub struct Discovery {
+    pub project_id: Id,
+    pub revision: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+}
+
+impl Discovery {
+    fn matches(&self, sim: &SimulationV7) -> bool {
+        (self.revision.is_none() || sim.revision == self.revision)
+            && self
+                .created_after
+                .is_none_or(|created_after| *sim.created_at > created_after)
+    }
+}
+
+/// Lists all simulations of the project matching `discovery`, optionally only those
+/// with the given status.
+pub async fn discover(
+    client: &Client,
+    discovery: &Discovery,
+    status: Option<SimulationsV7ListStatus>,
+) -> eyre::Result<Vec<SimulationV7>> {
+    let mut sims = vec![];
+    let mut offset = PaginationOffset(0u64);
+
+    loop {
+        let ListPageSimulationsV7 { items, nav } = client
+            .simulations_v7_list(
+                &discovery.project_id,
+                None,
+                None,
+                Some(&offset),
+                None,
+                status,
+                None,
+            )
+            .await
+            .map_err(fmt_progenitor_err)
+            .wrap_err_with(|| {
+                format!(
+                    "discovering simulations of project `{}`",
+                    discovery.project_id
+                )
+            })?
+            .into_inner();
+
+        sims.extend(items.into_iter().filter(|sim| discovery.matches(sim)));
+
+        if let Some(next_offset) = nav.next_offset {
+            offset = PaginationOffset(next_offset);
+        } else {
+            break;
+        }
+    }
+
+    Ok(sims)
+}
+
+/// Returned by [`fetch_all`] for simulations which do not exist (anymore), so that
+/// callers can tell them apart from transient failures.
+#[derive(Debug)]
+pub struct NotFound(pub Id);
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "simulation `{}` not found", self.0)
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// Fetches the given simulations concurrently, returning them in the same order.
+pub async fn fetch_all(
+    client: &Client,
+    ids: impl IntoIterator<Item = Id>,
+) -> eyre::Result<Vec<eyre::Result<SimulationV7>>> {
+    let mut set = JoinSet::new();
+
+    for (idx, id) in ids.into_iter().enumerate() {
+        let client = client.clone();
+
+        set.spawn(async move {
+            debug!("polling sim `{id}`...");
+
+            let res = match client.simulations_v7_get(&id).await {
+                Ok(sim) => Ok(sim),
+                Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => {
+                    Err(NotFound(id).into())
+                }
+                Err(err) => Err(fmt_progenitor_err(err))
+                    .wrap_err_with(|| format!("fetching simulation `{id}`")),
+            };
+
+            (idx, res.map(ResponseValue::into_inner))
+        });
+    }
+
+    let mut sims = vec![];
+
+    while let Some(joined) = set.join_next().await {
+        sims.push(joined?);
+    }
+
+    sims.sort_by_key(|(idx, _)| *idx);
+
+    Ok(sims.into_iter().map(|(_, sim)| sim).collect())
+}
@@ -0,0 +1,323 @@
+use crate::{
+    aerocloud::types::{SimulationResultsV7YawAnglesItem, SimulationV7},
+    fmt::NOT_AVAILABLE,
+};
+use serde::Serialize;
+
+const N_PER_LBF: f64 = 4.448_221_615_260_5;
+const N_PER_KGF: f64 = 9.806_65;
+const FT_PER_M: f64 = 3.280_839_895_013_123;
+const W_PER_HP: f64 = 745.699_871_582_270_2;
+const W_PER_PS: f64 = 735.498_75;
+const BTU_PER_H_PER_W: f64 = 3.412_141_633_127_942;
+
+/// Unit system results are displayed in. The API always reports SI values.
+#[derive(
+    clap::ValueEnum, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitSystem {
+    /// N, m², Nm, W
+    #[default]
+    Si,
+    /// lbf, ft², lbf·ft, hp, BTU/h·°F
+    Imperial,
+    /// kgf, m², kgf·m, PS
+    Technical,
+}
+
+/// Physical quantities carried by results, used to pick units and conversions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantity {
+    Length,
+    Area,
+    Force,
+    Moment,
+    Power,
+    HeatTransfer,
+    HeatTransferCoefficient,
+}
+
+impl UnitSystem {
+    /// Converts a SI `value` of `quantity` to this unit system, returning it along
+    /// with its unit.
+    pub fn convert(self, quantity: Quantity, value: f64) -> (f64, &'static str) {
+        use Quantity as Q;
+
+        let (factor, unit) = match (self, quantity) {
+            (Self::Si | Self::Technical, Q::Length) => (1.0, "m"),
+            (Self::Si | Self::Technical, Q::Area) => (1.0, "m²"),
+            (Self::Si, Q::Force) => (1.0, "N"),
+            (Self::Si, Q::Moment) => (1.0, "Nm"),
+            (Self::Si, Q::Power) => (1.0, "W"),
+            (Self::Si | Self::Technical, Q::HeatTransfer) => (1.0, "W/K"),
+            (Self::Si | Self::Technical, Q::HeatTransferCoefficient) => {
+                (1.0, "W/m²K")
+            }
+
+            (Self::Imperial, Q::Length) => (FT_PER_M, "ft"),
+            (Self::Imperial, Q::Area) => (FT_PER_M * FT_PER_M, "ft²"),
+            (Self::Imperial, Q::Force) => (1.0 / N_PER_LBF, "lbf"),
+            (Self::Imperial, Q::Moment) => (FT_PER_M / N_PER_LBF, "lbf·ft"),
+            (Self::Imperial, Q::Power) => (1.0 / W_PER_HP, "hp"),
+            (Self::Imperial, Q::HeatTransfer) => {
+                (BTU_PER_H_PER_W / 1.8, "BTU/h·°F")
+            }
+            (Self::Imperial, Q::HeatTransferCoefficient) => (
+                BTU_PER_H_PER_W / 1.8 / (FT_PER_M * FT_PER_M),
+                "BTU/h·ft²·°F",
+            ),
+
+            (Self::Technical, Q::Force) => (1.0 / N_PER_KGF, "kgf"),
+            (Self::Technical, Q::Moment) => (1.0 / N_PER_KGF, "kgf·m"),
+            (Self::Technical, Q::Power) => (1.0 / W_PER_PS, "PS"),
+        };
+
+        (value * factor, unit)
+    }
+
+    pub fn unit(self, quantity: Quantity) -> &'static str {
+        self.convert(quantity, 0.0).1
+    }
+}
+
+/// Quantities computed from the results of each yaw angle.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Derived {
+    /// Power needed to overcome drag at the fluid speed (Fd·v)
+    DragPower,
+    /// Lift to drag ratio (Fl/Fd)
+    LiftToDrag,
+    /// Longitudinal distance of the side force centre from the moment reference
+    /// point (My/Fs)
+    SideForceCentre,
+}
+
+impl Derived {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DragPower => "Drag power",
+            Self::LiftToDrag => "L/D",
+            Self::SideForceCentre => "Side force centre",
+        }
+    }
+
+    /// Name of the field holding the quantity in processed results.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::DragPower => "drag_power",
+            Self::LiftToDrag => "lift_to_drag",
+            Self::SideForceCentre => "side_force_centre",
+        }
+    }
+}
+
+/// How results are post-processed before being displayed.
+#[derive(Clone, Debug, Default)]
+pub struct Processing {
+    pub units: UnitSystem,
+    pub derived: Vec<Derived>,
+    /// Fluid speed in m/s forces and moments are rescaled to, assuming constant
+    /// coefficients.
+    pub at_speed: Option<f64>,
+}
+
+impl Processing {
+    /// Whether results are shown as returned by the API.
+    pub fn is_raw(&self) -> bool {
+        self.units == UnitSystem::Si
+            && self.derived.is_empty()
+            && self.at_speed.is_none()
+    }
+
+    /// Header of the columns produced by [`Processing::row`].
+    pub fn header(&self) -> Vec<String> {
+        [
+            "Surface",
+            "Fd",
+            "Fl",
+            "Fs",
+            "Cd",
+            "Cl",
+            "Cs",
+            "Cda",
+            "Cla",
+            "Csa",
+            "Mr",
+            "My",
+            "Mp",
+            "Heat transfer",
+            "Heat transfer coeff",
+        ]
+        .into_iter()
+        .chain(self.derived.iter().map(|d| d.label()))
+        .map(Into::into)
+        .collect()
+    }
+
+    /// Formats the results of a yaw angle of a simulation run at `fluid_speed`.
+    pub fn row(
+        &self,
+        fluid_speed: f64,
+        res: &SimulationResultsV7YawAnglesItem,
+    ) -> Vec<String> {
+        self.values(fluid_speed, res)
+            .into_iter()
+            .map(|(_, value)| match value {
+                Value::Measure(quantity, Some(v)) => self.fmt(quantity, v),
+                Value::Coefficient(v) => format!("{v:.3}"),
+                Value::Ratio(Some(v)) => format!("{v:.2}"),
+                Value::Measure(_, None) | Value::Ratio(None) => {
+                    NOT_AVAILABLE.into()
+                }
+            })
+            .collect()
+    }
+
+    /// Results of `sim` converted to the unit system, rescaled and with the
+    /// derived quantities, for output alongside the raw SI results.
+    fn processed(&self, sim: &SimulationV7) -> Option<Processed> {
+        let results = sim.results.as_ref()?;
+
+        let yaw_angles = results
+            .yaw_angles
+            .iter()
+            .map(|res| {
+                let mut values = serde_json::Map::new();
+                values.insert("yaw_angle".into(), res.yaw_angle.0.into());
+
+                for (key, value) in self.values(sim.params.fluid_speed.0, res) {
+                    let value = match value {
+                        Value::Measure(quantity, v) => {
+                            let unit = self.units.unit(quantity);
+                            serde_json::json!({
+                                "value": v.map(|v| self.units.convert(quantity, v).0),
+                                "unit": unit,
+                            })
+                        }
+                        Value::Coefficient(v) => v.into(),
+                        Value::Ratio(v) => v.into(),
+                    };
+
+                    values.insert(key.into(), value);
+                }
+
+                values
+            })
+            .collect();
+
+        Some(Processed {
+            units: self.units,
+            at_speed: self.at_speed,
+            yaw_angles,
+        })
+    }
+
+    /// Pairs `simulation` with its processed results, which are left out when
+    /// results are shown as returned by the API.
+    pub fn with_processed<'a>(
+        &self,
+        simulation: &'a SimulationV7,
+    ) -> WithProcessed<'a> {
+        WithProcessed {
+            simulation,
+            processed: if self.is_raw() {
+                None
+            } else {
+                self.processed(simulation)
+            },
+        }
+    }
+
+    /// Values of the columns of [`Processing::header`], still in SI, keyed by
+    /// their field name.
+    fn values(
+        &self,
+        fluid_speed: f64,
+        res: &SimulationResultsV7YawAnglesItem,
+    ) -> Vec<(&'static str, Value)> {
+        use Quantity as Q;
+
+        let speed = self.at_speed.unwrap_or(fluid_speed);
+        let scale = if fluid_speed > 0.0 {
+            (speed / fluid_speed).powi(2)
+        } else {
+            1.0
+        };
+        let measure = |quantity, value| Value::Measure(quantity, Some(value));
+
+        let mut values = vec![
+            ("surface_area", measure(Q::Area, res.surface_area)),
+            ("fd", measure(Q::Force, res.fd * scale)),
+            ("fl", measure(Q::Force, res.fl * scale)),
+            ("fs", measure(Q::Force, res.fs * scale)),
+            ("cd", Value::Coefficient(res.cd)),
+            ("cl", Value::Coefficient(res.cl)),
+            ("cs", Value::Coefficient(res.cs)),
+            ("cda", measure(Q::Area, res.cda)),
+            ("cla", measure(Q::Area, res.cla)),
+            ("csa", measure(Q::Area, res.csa)),
+            ("mr", measure(Q::Moment, res.mr * scale)),
+            ("my", measure(Q::Moment, res.my * scale)),
+            ("mp", measure(Q::Moment, res.mp * scale)),
+            ("heat_transfer", measure(Q::HeatTransfer, res.heat_transfer)),
+            (
+                "heat_transfer_coefficient",
+                measure(
+                    Q::HeatTransferCoefficient,
+                    res.heat_transfer_coefficient,
+                ),
+            ),
+        ];
+
+        values.extend(self.derived.iter().map(|derived| {
+            let value = match derived {
+                Derived::DragPower => measure(Q::Power, res.fd * scale * speed),
+                Derived::LiftToDrag => Value::Ratio(ratio(res.fl, res.fd)),
+                Derived::SideForceCentre => {
+                    Value::Measure(Q::Length, ratio(res.my, res.fs))
+                }
+            };
+
+            (derived.key(), value)
+        }));
+
+        values
+    }
+
+    fn fmt(&self, quantity: Quantity, value: f64) -> String {
+        let (value, unit) = self.units.convert(quantity, value);
+        format!("{value:.2} {unit}")
+    }
+}
+
+/// A processed result in SI, or unavailable when it can't be computed.
+enum Value {
+    Measure(Quantity, Option<f64>),
+    Coefficient(f64),
+    Ratio(Option<f64>),
+}
+
+/// Processed results of a simulation, output next to its raw `results` by
+/// `--json` and `--template`.
+#[derive(Serialize, Debug)]
+pub struct Processed {
+    pub units: UnitSystem,
+    pub at_speed: Option<f64>,
+    /// Results of each yaw angle, quantities with units being
+    /// `{"value": .., "unit": ..}` objects.
+    pub yaw_angles: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// A simulation along with its [`Processed`] results, if any were requested.
+#[derive(Serialize, Debug)]
+pub struct WithProcessed<'a> {
+    #[serde(flatten)]
+    pub simulation: &'a SimulationV7,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processed: Option<Processed>,
+}
+
+fn ratio(num: f64, den: f64) -> Option<f64> {
+    (den.abs() > f64::EPSILON).then(|| num / den)
+}
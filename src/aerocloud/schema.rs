@@ -0,0 +1,334 @@
+use itertools::Itertools;
+use serde_json::{Map, Number, Value, json};
+use std::{fmt, sync::LazyLock};
+
+/// The same schema the client is generated from, to check params against before
+/// they reach the API.
+static SCHEMA: LazyLock<Value> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../schemas/aerocloud.json"))
+        .expect("embedded schema is valid json")
+});
+
+/// Params as they're written locally, made of definitions from the API schema.
+#[derive(Debug, Clone, Copy)]
+pub enum Definition {
+    Simulation,
+    Model,
+    File,
+    Filename,
+}
+
+impl Definition {
+    fn schema(self) -> Value {
+        match self {
+            Self::Simulation => component("CreateSimulationV7Params"),
+            Self::Model => {
+                let mut files = SCHEMA
+                    .pointer(
+                        "/components/schemas/CreateModelV7Params/properties/files",
+                    )
+                    .cloned()
+                    .unwrap_or_else(|| json!({ "type": "array" }));
+
+                // NOTE: files are given by path locally, named after it once sent.
+                files["items"] = Self::File.schema();
+                files["items"]["properties"]["path"] =
+                    json!({ "type": "string" });
+
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "reusable": { "type": "boolean" },
+                        "files": files,
+                    },
+                })
+            }
+            Self::File => json!({
+                "type": "object",
+                "properties": {
+                    "unit": component("FileUnit"),
+                    "rotation": component("Quaternion"),
+                    "parts": {
+                        "type": "object",
+                        "additionalProperties": component("UpdatePartV7Params"),
+                    },
+                },
+            }),
+            Self::Filename => component("Filename"),
+        }
+    }
+}
+
+/// A value breaking a constraint of the schema.
+#[derive(Debug, Clone)]
+pub struct Violation {
+    /// JSON pointer to the value, relative to the one validated.
+    pub pointer: String,
+    pub problem: String,
+    pub fix: Option<String>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.pointer.is_empty() {
+            write!(f, "`{}`: ", self.pointer)?;
+        }
+
+        f.write_str(&self.problem)?;
+
+        if let Some(fix) = &self.fix {
+            write!(f, ", {fix}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks `value` against `definition`, collecting every violation rather than
+/// stopping at the first one.
+///
+/// Required keys aren't checked, as params missing locally are filled from
+/// defaults, and neither are `null`s, which stand for unset optional params.
+pub fn validate(value: &Value, definition: Definition) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    check(value, &definition.schema(), "", &mut violations);
+
+    violations
+}
+
+fn component(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+fn check(
+    value: &Value,
+    schema: &Value,
+    pointer: &str,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if let Some(resolved) = reference
+            .strip_prefix('#')
+            .and_then(|reference| SCHEMA.pointer(reference))
+        {
+            check(value, resolved, pointer, violations);
+        }
+
+        return;
+    }
+
+    if value.is_null() {
+        return;
+    }
+
+    let mut violate = |problem: String, fix: Option<String>| {
+        violations.push(Violation {
+            pointer: pointer.to_owned(),
+            problem,
+            fix,
+        });
+    };
+
+    let example = || {
+        schema
+            .get("example")
+            .map(|example| format!("e.g. `{example}`"))
+    };
+
+    if let Some(ty) = schema.get("type").and_then(Value::as_str)
+        && !is_of_type(value, ty)
+    {
+        violate(
+            format!("expected {}, found {}", article(ty), kind(value)),
+            example(),
+        );
+        return;
+    }
+
+    if let Some(variants) = schema.get("enum").and_then(Value::as_array)
+        && !variants.contains(value)
+    {
+        let fix = match closest(
+            value.as_str(),
+            variants.iter().filter_map(Value::as_str),
+        ) {
+            Some(variant) => format!("did you mean `{variant}`?"),
+            None => format!(
+                "use one of {}",
+                variants.iter().map(|v| format!("`{v}`")).join(", ")
+            ),
+        };
+
+        violate(format!("{value} is not allowed"), Some(fix));
+        return;
+    }
+
+    match value {
+        Value::Number(n) => {
+            let min = schema.get("minimum").and_then(Value::as_number);
+            let max = schema.get("maximum").and_then(Value::as_number);
+            let n = n.as_f64().unwrap_or_default();
+
+            let below =
+                min.is_some_and(|min| n < min.as_f64().unwrap_or_default());
+            let above =
+                max.is_some_and(|max| n > max.as_f64().unwrap_or_default());
+
+            if below || above {
+                violate(
+                    format!("{value} is out of range"),
+                    Some(range_fix(min, max)),
+                );
+            }
+        }
+        Value::String(s) => {
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+                && let Ok(re) = regress::Regex::new(pattern)
+                && re.find(s).is_none()
+            {
+                violate(format!("{value} doesn't match `{pattern}`"), example());
+            }
+
+            if let Some("uuid") = schema.get("format").and_then(Value::as_str)
+                && uuid::Uuid::parse_str(s).is_err()
+            {
+                violate(format!("{value} is not a valid id"), example());
+            }
+        }
+        Value::Array(items) => check_array(items, schema, pointer, violations),
+        Value::Object(map) => check_object(map, schema, pointer, violations),
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+fn check_array(
+    items: &[Value],
+    schema: &Value,
+    pointer: &str,
+    violations: &mut Vec<Violation>,
+) {
+    let min = schema.get("minItems").and_then(Value::as_number);
+    let max = schema.get("maxItems").and_then(Value::as_number);
+    let len = items.len() as u64;
+
+    let below = min.and_then(Number::as_u64).is_some_and(|min| len < min);
+    let above = max.and_then(Number::as_u64).is_some_and(|max| len > max);
+
+    if below || above {
+        violations.push(Violation {
+            pointer: pointer.to_owned(),
+            problem: format!("has {len} items"),
+            fix: Some(match (min, max) {
+                (Some(min), Some(max)) if min == max => {
+                    format!("use exactly {min} items")
+                }
+                (Some(min), Some(max)) => {
+                    format!("use between {min} and {max} items")
+                }
+                (Some(min), None) => format!("use at least {min} items"),
+                (None, Some(max)) => format!("use at most {max} items"),
+                (None, None) => unreachable!(),
+            }),
+        });
+    }
+
+    if let Some(item_schema) = schema.get("items") {
+        for (idx, item) in items.iter().enumerate() {
+            check(item, item_schema, &format!("{pointer}/{idx}"), violations);
+        }
+    }
+}
+
+fn check_object(
+    map: &Map<String, Value>,
+    schema: &Value,
+    pointer: &str,
+    violations: &mut Vec<Violation>,
+) {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let additional = schema.get("additionalProperties");
+
+    for (key, item) in map {
+        let item_pointer = format!("{pointer}/{}", escape(key));
+
+        if let Some(item_schema) = properties
+            .and_then(|properties| properties.get(key))
+            .or(additional)
+        {
+            check(item, item_schema, &item_pointer, violations);
+            continue;
+        }
+
+        // NOTE: unknown keys are ignored by the API, only those looking
+        // like a typo are worth pointing out.
+        if let Some(known) = properties.and_then(|properties| {
+            closest(Some(key), properties.keys().map(String::as_str))
+        }) {
+            violations.push(Violation {
+                pointer: item_pointer,
+                problem: "unknown param, it would be ignored".into(),
+                fix: Some(format!("did you mean `{known}`?")),
+            });
+        }
+    }
+}
+
+fn is_of_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn article(ty: &str) -> String {
+    match ty {
+        "array" | "object" | "integer" => format!("an {ty}"),
+        _ => format!("a {ty}"),
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn range_fix(min: Option<&Number>, max: Option<&Number>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("use a value between {min} and {max}"),
+        (Some(min), None) => format!("use a value of at least {min}"),
+        (None, Some(max)) => format!("use a value of at most {max}"),
+        (None, None) => unreachable!(),
+    }
+}
+
+/// The candidate `s` is most likely a misspelling of, if any.
+fn closest<'a>(
+    s: Option<&str>,
+    candidates: impl Iterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let s = s?;
+
+    candidates
+        .map(|candidate| (strsim::levenshtein(s, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Escapes a key to be used as a JSON pointer token.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
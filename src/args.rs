@@ -1,21 +1,33 @@
 use crate::{
     aerocloud::{
         NEW_TOKEN_URL,
+        cache::Cache,
+        metrics::Metric,
+        results::{Derived, UnitSystem},
         types::{
             FluidSpeed, Id, ProjectStatus, SimulationQuality,
             SimulationsV6ListStatus, SimulationsV7ListStatus, YawAngle,
         },
     },
+    commands::aerocloud::v7::{batch::Discovery, report::ReportFormat},
+    completion,
     config::{Config, Token},
+    daemon::{self, store::Store},
+    filter::Filter,
+    git,
+    notify::Notifier,
+    template::Template,
+    utils::{parse_datetime, parse_speed},
 };
+use chrono::{DateTime, Utc};
 use clap::{
     Parser, Subcommand,
     builder::styling::{AnsiColor, Styles},
 };
-use clap_complete::aot::Shell;
+use clap_complete::{aot::Shell, engine::ArgValueCandidates};
 use clap_stdin::{FileOrStdin, MaybeStdin};
 use reqwest::Url;
-use std::{path::PathBuf, time::Duration};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
 const STYLES: Styles = Styles::styled()
     .header(AnsiColor::Green.on_default().bold())
@@ -24,6 +36,7 @@ const STYLES: Styles = Styles::styled()
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None, styles = STYLES)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Args {
     #[arg(
         short,
@@ -49,6 +62,37 @@ pub struct Args {
     )]
     pub json: bool,
 
+    #[arg(
+        short = 'T',
+        long,
+        value_name = "NAME|FILE|TEMPLATE",
+        env = "NF_TEMPLATE",
+        value_parser = Template::resolve,
+        conflicts_with = "json",
+        help = format!(r"Render each returned object through a template instead of human-friendly tables, e.g. `{{{{name}}}} rev {{{{revision}}}}: Cd={{{{results.yaw_angles[0].cd}}}}`.
+
+Accepts the name of a built-in template ({}), a path to a template file or the template itself. Paths (with a `/` or a `.j2`, `.md`, `.html`, ... extension) must exist.", Template::builtin_names())
+    )]
+    pub template: Option<Template>,
+
+    #[arg(
+        short = 'n',
+        long,
+        value_name = "NOTIFIER",
+        env = "NF_NOTIFY",
+        help = "Notify about finished work via `desktop`, `webhook:<URL>` or `command:<COMMAND>` (can be repeated). When specified, it will take precedence over the notifiers set in config"
+    )]
+    pub notify: Vec<Notifier>,
+
+    #[arg(
+        long,
+        value_name = "ADDR",
+        env = "NF_DAEMON_ADDR",
+        default_value = daemon::DEFAULT_ADDR,
+        help = "Address of the local daemon started with `nf daemon run`. Requests are authenticated with a token it writes to a file only its user can read"
+    )]
+    pub daemon_addr: SocketAddr,
+
     #[arg(
         short = 't',
         long,
@@ -75,6 +119,15 @@ pub struct Args {
     )]
     pub aerocloud_auth_token: Option<Token>,
 
+    #[arg(
+        short = 'P',
+        long,
+        value_name = "NAME",
+        env = "NF_PROFILE",
+        help = "Profile in the config to take the token and hostname from. When specified, it will take precedence over the one set in `.nablaflow.toml`"
+    )]
+    pub profile: Option<String>,
+
     #[arg(
         short,
         long,
@@ -85,6 +138,22 @@ pub struct Args {
     )]
     pub config_path: PathBuf,
 
+    #[arg(
+        long,
+        env = "NF_OFFLINE",
+        help = "List projects, simulations and models from the local cache instead of fetching them"
+    )]
+    pub offline: bool,
+
+    #[arg(
+        long,
+        default_value_os_t = Cache::default_dir().expect("failed to determine default cache dir"),
+        value_name = "DIR",
+        env = "NF_CACHE_DIR",
+        help = "Folder where fetched projects, simulations and models are cached"
+    )]
+    pub cache_dir: PathBuf,
+
     #[arg(
         short,
         long,
@@ -118,7 +187,33 @@ pub enum Scope {
     },
 
     #[command(
-        about = "Generate completions for major shells (bash, elvish, fish, powershell, zsh)"
+        about = "Track submitted simulations in the background, downloading results and notifying on completion"
+    )]
+    Daemon {
+        #[command(subcommand)]
+        command: DaemonScope,
+    },
+
+    #[command(
+        about = "Start an interactive shell running v7 commands with history, completion and a persistent client"
+    )]
+    Shell,
+
+    #[command(
+        about = "Manage the local cache of projects, simulations and models"
+    )]
+    Cache {
+        #[command(subcommand)]
+        command: CacheScope,
+    },
+
+    #[command(
+        about = "Generate completions for major shells (bash, elvish, fish, powershell, zsh)",
+        after_help = "These completions are static. To also complete project, simulation and model ids, register dynamic completions instead:
+
+  bash: source <(COMPLETE=bash nf)
+  zsh:  source <(COMPLETE=zsh nf)
+  fish: COMPLETE=fish nf | source"
     )]
     GenerateCompletions { shell: Shell },
 
@@ -156,8 +251,141 @@ pub enum AeroCloudScope {
     },
 }
 
+/// A line typed in `nf shell`, parsed with the same definitions as the v7
+/// commands.
+#[derive(Parser, Debug)]
+#[command(multicall = true, about = "Commands available in `nf shell`", styles = STYLES)]
+pub struct ShellLine {
+    #[command(subcommand)]
+    pub command: ShellCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ShellCommand {
+    #[command(about = "Set the context later commands default to")]
+    Use {
+        #[command(subcommand)]
+        target: UseTarget,
+    },
+
+    #[command(visible_alias = "quit", about = "Leave the shell")]
+    Exit,
+
+    #[command(flatten)]
+    V7(AeroCloudV7Command),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum UseTarget {
+    #[command(
+        about = "Default to the given project when no project id is passed to commands"
+    )]
+    Project {
+        #[arg(
+            add = ArgValueCandidates::new(completion::project_ids),
+            help = "Name or id of a project from `list-projects`"
+        )]
+        project: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheScope {
+    #[command(about = "Remove all cached listings")]
+    Clear,
+
+    #[command(about = "Show cached listings along with their size and age")]
+    Stats,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DaemonScope {
+    #[command(
+        about = "Run the daemon in the foreground. Simulations created with `nf` are tracked automatically while it runs"
+    )]
+    Run {
+        #[arg(
+            long = "store",
+            value_name = "PATH",
+            default_value_os_t = Store::default_path().expect("failed to determine default data dir"),
+            env = "NF_DAEMON_STORE",
+            help = "File tracked simulations are persisted to"
+        )]
+        store_path: PathBuf,
+
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Download attachments of succeeded simulations to the given folder"
+        )]
+        download_dir: Option<PathBuf>,
+
+        #[arg(
+            long = "min-interval",
+            value_name = "SECS",
+            default_value_t = 30,
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Seconds to wait before polling a simulation again after it changed"
+        )]
+        min_interval_secs: u64,
+
+        #[arg(
+            long = "max-interval",
+            value_name = "SECS",
+            default_value_t = 600,
+            help = "Seconds the polling interval backs off to while a simulation does not change"
+        )]
+        max_interval_secs: u64,
+    },
+
+    #[command(about = "Start tracking simulations in the running daemon")]
+    Track {
+        #[arg(
+            add = ArgValueCandidates::new(completion::simulation_ids),
+            required = true,
+            help = "List of simulation ids from `nf aerocloud v7 list-simulations`"
+        )]
+        ids: Vec<Id>,
+    },
+
+    #[command(about = "Show simulations tracked by the running daemon")]
+    Status {
+        #[arg(
+            add = ArgValueCandidates::new(completion::simulation_ids),
+            help = "Only show the given simulations"
+        )]
+        ids: Vec<Id>,
+    },
+
+    #[command(
+        about = "Wait for simulations to be done using the running daemon, failing unless all succeeded"
+    )]
+    Wait {
+        #[arg(
+            add = ArgValueCandidates::new(completion::simulation_ids),
+            required = true,
+            help = "List of simulation ids from `nf aerocloud v7 list-simulations`"
+        )]
+        ids: Vec<Id>,
+
+        #[arg(
+            long = "timeout",
+            value_name = "SECS",
+            help = "Give up waiting after the given amount of seconds"
+        )]
+        timeout_secs: Option<u64>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
-#[command(about = "Make changes to the config")]
+#[command(
+    about = "Make changes to the config",
+    long_about = "Make changes to the config.
+
+Tokens and hostnames can be stored under named profiles, selected with `--profile`. Setting the token or hostname while a profile is selected changes that profile, creating it if needed.
+
+Some settings can also be pinned per directory in a `.nablaflow.toml` file, looked up in the current dir and its parents: `hostname`, `profile`, `project_id`, `batch_root`, `git_revision` and `[simulation]` defaults. A `hostname` other than the one of the profile in use is only connected to once trusted with `nf config trust-hostname`, since the token is sent to it."
+)]
 pub enum ConfigScope {
     #[command(about = "Change the API hostname")]
     SetHostname {
@@ -168,6 +396,14 @@ pub enum ConfigScope {
     #[command(about = "Unset a custom API hostname")]
     UnsetHostname,
 
+    #[command(
+        about = "Allow `.nablaflow.toml` files to point to the given hostname, sending the token to it"
+    )]
+    TrustHostname {
+        #[arg(value_name = "HOSTNAME", help = "Hostname to trust")]
+        hostname: Url,
+    },
+
     #[command(about = "Show the config")]
     Show,
 }
@@ -248,8 +484,11 @@ pub enum AeroCloudV7Command {
 
     #[command(about = "List simulations")]
     ListSimulations {
-        #[arg(help = "A project id from `nf aerocloud v7 list-projects`")]
-        project_id: Id,
+        #[arg(
+            add = ArgValueCandidates::new(completion::project_ids),
+            help = "A project id from `nf aerocloud v7 list-projects`. Defaults to `project_id` from `.nablaflow.toml`"
+        )]
+        project_id: Option<Id>,
 
         #[arg(
             short = 'r',
@@ -270,6 +509,62 @@ pub enum AeroCloudV7Command {
 
         #[arg(short = 'y', long, help = "Filter by yaw angle")]
         yaw_angle: Option<YawAngle>,
+
+        #[arg(
+            short = 'w',
+            long = "where",
+            value_name = "EXPR",
+            help = r#"Filter locally on any field of the returned simulations, e.g. `revision ~ "v3*" && created_at > 2026-09-01 && results.cd < 0.3`.
+
+Supports `==`, `!=`, `<`, `<=`, `>`, `>=`, `~` (glob match) and `!~`, combined with `&&`, `||`, `!` and parentheses.
+Arrays can be indexed (`params.yaw_angles[0]`), otherwise a comparison holds when any element satisfies it.
+Coefficients in `results` (`results.cd`) stand for those at any yaw angle, `results.yaw_angles[0].cd` picks one."#
+        )]
+        filter: Option<Filter>,
+
+        #[arg(
+            short = 'u',
+            long,
+            value_enum,
+            default_value_t = UnitSystem::Si,
+            help = "Unit system to display results in, added under `processed` with `--json` and `--template`"
+        )]
+        units: UnitSystem,
+
+        #[arg(
+            short = 'd',
+            long,
+            value_enum,
+            value_delimiter = ',',
+            help = "Derived quantities to add to results (comma separated or repeated), under `processed` with `--json` and `--template`"
+        )]
+        derived: Vec<Derived>,
+
+        #[arg(
+            long,
+            value_name = "M/S",
+            value_parser = parse_speed,
+            help = "Rescale forces, moments and drag power to this fluid speed, assuming constant coefficients"
+        )]
+        at_speed: Option<f64>,
+    },
+
+    #[command(about = "Show a simulation along with its results")]
+    GetSimulation {
+        #[arg(
+            add = ArgValueCandidates::new(completion::simulation_ids),
+            help = "A simulation id from `nf aerocloud v7 list-simulations`"
+        )]
+        simulation_id: Id,
+
+        #[arg(
+            short = 'u',
+            long,
+            value_enum,
+            default_value_t = UnitSystem::Si,
+            help = "Unit system to display results in, added under `processed` with `--json` and `--template`"
+        )]
+        units: UnitSystem,
     },
 
     #[command(about = "List reusable models")]
@@ -279,6 +574,7 @@ pub enum AeroCloudV7Command {
     CreateModel {
         #[arg(
             help = format!(r#"Path to file containing params (pass - for reading file from stdin).
+JSON, YAML (`.yaml`/`.yml`) or TOML (`.toml`), picked by extension, stdin is read as JSON.
 
 Example:
 
@@ -305,6 +601,7 @@ Example:
     #[command(about = "Delete simulations")]
     DeleteSimulations {
         #[arg(
+            add = ArgValueCandidates::new(completion::simulation_ids),
             required = true,
             help = "A list of simulation ids from `nf aerocloud v7 list-simulations`"
         )]
@@ -314,6 +611,7 @@ Example:
     #[command(about = "Create a new simulation")]
     CreateSimulation {
         #[arg(
+            add = ArgValueCandidates::new(completion::model_ids),
             short,
             long,
             help = "A model id from `nf aerocloud v7 create-model`. When set, it will have precedence over what is read from <PARAMS>"
@@ -321,6 +619,7 @@ Example:
         model_id: Option<Id>,
 
         #[arg(
+            add = ArgValueCandidates::new(completion::project_ids),
             short,
             long,
             help = "A project id from `nf aerocloud v7 create-project`. When set, it will have precedence over what is read from <PARAMS>"
@@ -329,6 +628,7 @@ Example:
 
         #[arg(
             help = format!(r#"Path to file containing params (pass - to read file from stdin).
+JSON, YAML (`.yaml`/`.yml`) or TOML (`.toml`), picked by extension, stdin is read as JSON.
 
 Example:
 
@@ -337,31 +637,270 @@ Example:
 "#, include_str!("../examples/aerocloud/v7/create_simulation.json"))
         )]
         params: FileOrStdin,
+
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = git::DEFAULT_REVISION_FORMAT,
+            help = format!("Fill `revision` from the git repository containing <PARAMS>, unless already set. Placeholders are {}. Defaults to `git_revision` from `.nablaflow.toml`", git::REVISION_PLACEHOLDERS)
+        )]
+        git_revision: Option<String>,
     },
 
-    #[command(about = "Wait for one or many simulations to succeed.")]
+    #[command(
+        visible_alias = "wait",
+        about = "Wait for one or many simulations to succeed.",
+        after_help = "Exits with 0 when all simulations succeeded, 3 when any expired, 4 when any is a draft and 5 when the timeout elapsed first. The highest applicable code wins."
+    )]
     WaitForSimulations {
         #[arg(
-            required = true,
+            add = ArgValueCandidates::new(completion::simulation_ids),
+            required_unless_present = "project",
+            help = "List of simulation ids from `nf aerocloud v7 list-simulations`"
+        )]
+        ids: Vec<Id>,
+
+        #[arg(
+            add = ArgValueCandidates::new(completion::project_ids),
+            short = 'p',
+            long,
+            value_name = "PROJECT_ID",
+            help = "Wait for all in-progress simulations of a project, including those submitted while waiting"
+        )]
+        project: Option<Id>,
+
+        #[arg(
+            short = 'r',
+            long,
+            requires = "project",
+            help = "Only discover simulations of the given revision"
+        )]
+        revision: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DATETIME",
+            requires = "project",
+            value_parser = parse_datetime,
+            help = "Only discover simulations created after the given RFC3339 timestamp or YYYY-MM-DD date"
+        )]
+        created_after: Option<DateTime<Utc>>,
+
+        #[arg(
+            long = "interval",
+            value_name = "SECS",
+            default_value_t = 60,
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Seconds to wait between polls"
+        )]
+        interval_secs: u64,
+
+        #[arg(
+            long = "timeout",
+            value_name = "SECS",
+            help = "Give up waiting after the given amount of seconds"
+        )]
+        timeout_secs: Option<u64>,
+    },
+
+    #[command(
+        about = "Print a JSON line whenever simulations are first seen or change status, until interrupted"
+    )]
+    Watch {
+        #[arg(
+            add = ArgValueCandidates::new(completion::simulation_ids),
+            required_unless_present = "project",
             help = "List of simulation ids from `nf aerocloud v7 list-simulations`"
         )]
         ids: Vec<Id>,
+
+        #[arg(
+            add = ArgValueCandidates::new(completion::project_ids),
+            short = 'p',
+            long,
+            value_name = "PROJECT_ID",
+            help = "Watch all simulations of a project, including those created while watching"
+        )]
+        project: Option<Id>,
+
+        #[arg(
+            short = 'r',
+            long,
+            requires = "project",
+            help = "Only watch simulations of the given revision"
+        )]
+        revision: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DATETIME",
+            requires = "project",
+            value_parser = parse_datetime,
+            help = "Only watch simulations created after the given RFC3339 timestamp or YYYY-MM-DD date"
+        )]
+        created_after: Option<DateTime<Utc>>,
+
+        #[arg(
+            long = "interval",
+            value_name = "SECS",
+            default_value_t = 60,
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Seconds to wait between polls"
+        )]
+        interval_secs: u64,
     },
 
     #[command(about = "Delete projects")]
     DeleteProjects {
-        #[arg(required = true)]
+        #[arg(
+            add = ArgValueCandidates::new(completion::project_ids),
+            required = true
+        )]
         project_ids: Vec<Id>,
     },
 
+    #[command(about = "Plot a result metric against yaw angle in the terminal")]
+    Plot {
+        #[arg(
+            add = ArgValueCandidates::new(completion::simulation_ids),
+            required = true,
+            help = "List of simulation ids from `nf aerocloud v7 list-simulations`"
+        )]
+        simulation_ids: Vec<Id>,
+
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value_t = Metric::Cd,
+            help = "Metric to plot"
+        )]
+        metric: Metric,
+
+        #[arg(
+            short = 'u',
+            long,
+            value_enum,
+            default_value_t = UnitSystem::Si,
+            help = "Unit system to plot the metric in"
+        )]
+        units: UnitSystem,
+
+        #[arg(long, default_value_t = 24, help = "Height of the chart in lines")]
+        height: u16,
+    },
+
+    #[command(
+        about = "Generate a self-contained report with parameters, results and charts for a project"
+    )]
+    Report {
+        #[arg(
+            add = ArgValueCandidates::new(completion::project_ids),
+            help = "A project id from `nf aerocloud v7 list-projects`. Defaults to `project_id` from `.nablaflow.toml`"
+        )]
+        project_id: Option<Id>,
+
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value_t = ReportFormat::Html,
+            help = "Format of the report"
+        )]
+        format: ReportFormat,
+
+        #[arg(
+            short,
+            long = "metric",
+            value_enum,
+            default_values_t = [Metric::Cd, Metric::Cl, Metric::Cs],
+            help = "Metrics to chart and compare across revisions (can be repeated)"
+        )]
+        metrics: Vec<Metric>,
+
+        #[arg(
+            short = 'u',
+            long,
+            value_enum,
+            default_value_t = UnitSystem::Si,
+            help = "Unit system to display results in"
+        )]
+        units: UnitSystem,
+
+        #[arg(
+            short = 'd',
+            long,
+            value_enum,
+            value_delimiter = ',',
+            help = "Derived quantities to add to results (comma separated or repeated)"
+        )]
+        derived: Vec<Derived>,
+
+        #[arg(
+            long,
+            value_name = "M/S",
+            value_parser = parse_speed,
+            help = "Rescale forces, moments and drag power to this fluid speed, assuming constant coefficients"
+        )]
+        at_speed: Option<f64>,
+
+        #[arg(
+            short,
+            long,
+            help = "Write the report to the given path instead of stdout"
+        )]
+        output: Option<PathBuf>,
+    },
+
     #[command(
         about = "Start an interactive UI to review and submit multiple simulations at once."
     )]
     Batch {
         #[arg(
             required = false,
-            help = "Root dir with simulations, their models and params."
+            help = "Root dir with simulations, their models and params. Params are read from `params.json`, `params.yaml`/`params.yml` or `params.toml`, picked by extension. Values missing from them are taken from `defaults` (in any of those formats) in the simulation dir or any of its parents up to the root dir, the closest winning. Defaults to `batch_root` from `.nablaflow.toml`"
         )]
         root_dir: Option<PathBuf>,
+
+        #[arg(
+            short,
+            long,
+            help = "Look for simulations in nested dirs too, in those containing the marker file, naming them after their path relative to the root dir"
+        )]
+        recursive: bool,
+
+        #[arg(
+            long,
+            value_name = "FILE",
+            requires = "recursive",
+            help = "File marking a dir as a simulation when looking for them recursively. Defaults to params in any of the accepted formats"
+        )]
+        marker: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "GLOB",
+            help = "Only pick up simulations whose name matches (can be repeated). `*` matches any characters including `/`, globs without `/` are also matched against the last dir alone"
+        )]
+        include: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "GLOB",
+            help = format!("Skip dirs whose name matches, along with their content (can be repeated). Globs are also read from `{}` in the root dir, one per line", Discovery::IGNORE_FILENAME)
+        )]
+        exclude: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = git::DEFAULT_REVISION_FORMAT,
+            help = format!("Fill `revision` from the git repository containing each simulation dir, unless already set. Placeholders are {}. Defaults to `git_revision` from `.nablaflow.toml`", git::REVISION_PLACEHOLDERS)
+        )]
+        git_revision: Option<String>,
     },
 }
@@ -8,7 +8,9 @@ use color_eyre::eyre;
 pub async fn run(args: &Args, client: &Client) -> eyre::Result<()> {
     let user = client.users_self().await?.into_inner();
 
-    if args.json {
+    if let Some(template) = &args.template {
+        template.print(&user)?;
+    } else if args.json {
         println!("{}", &serde_json::to_string(&user)?);
     } else {
         print_human(&user);
@@ -1,10 +1,13 @@
 use crate::{
+    aerocloud::{Client, cache::Cache, poll::Discovery, results::Processing},
     args::{AeroCloudScope, AeroCloudV6Command, AeroCloudV7Command, Args},
+    commands::aerocloud::v7::batch::ParamsFill,
     config::Config,
     http,
+    notify::Notifiers,
 };
 use color_eyre::eyre::{self, WrapErr};
-use std::path::PathBuf;
+use std::{path::Path, time::Duration};
 
 pub mod current_token;
 pub mod current_user;
@@ -12,7 +15,6 @@ pub mod set_auth_token;
 pub mod v6;
 pub mod v7;
 
-#[allow(clippy::too_many_lines)]
 pub async fn run(
     args: &Args,
     config: Config,
@@ -24,6 +26,7 @@ pub async fn run(
 
     let client =
         http::build_aerocloud_client_from_config(&config, &args.http_timeout())?;
+    let cache = Cache::new(args);
 
     match subcommand {
         AeroCloudScope::SetAuthToken { .. } => Ok(()),
@@ -74,93 +77,241 @@ pub async fn run(
                     .await
             }
         },
-        AeroCloudScope::V7 { command } => match command {
-            AeroCloudV7Command::ListProjects { status } => {
-                self::v7::list_projects::run(args, &client, *status).await
-            }
-            AeroCloudV7Command::CreateProject { name, description } => {
-                self::v7::create_project::run(
-                    args,
-                    &client,
-                    name,
-                    description.as_deref(),
-                )
-                .await
-            }
-            AeroCloudV7Command::DeleteProjects { project_ids } => {
-                self::v7::delete_projects::run(args, &client, project_ids).await
-            }
-            AeroCloudV7Command::ListSimulations {
-                project_id,
-                show_results,
-                status,
-                fluid_speed,
-                quality,
-                yaw_angle,
-            } => {
-                self::v7::list_simulations::run(
-                    args,
-                    &client,
-                    project_id,
-                    *show_results,
-                    *status,
-                    *quality,
-                    fluid_speed.clone(),
-                    yaw_angle.clone(),
-                )
-                .await
-            }
-            AeroCloudV7Command::ListReusableModels => {
-                self::v7::list_reusable_models::run(args, &client).await
-            }
-            AeroCloudV7Command::CreateModel { params } => {
-                self::v7::create_model::run(
-                    args,
-                    &client,
-                    &params
-                        .clone()
-                        .contents()
-                        .wrap_err("failed to read contents")?,
-                )
+        AeroCloudScope::V7 { command } => {
+            run_v7(args, &config, &client, &cache, command).await
+        }
+    }
+}
+
+/// Runs a v7 command with an already built client, so that it can be reused
+/// across commands by `nf shell`.
+#[allow(clippy::too_many_lines)]
+pub async fn run_v7(
+    args: &Args,
+    config: &Config,
+    client: &Client,
+    cache: &Cache,
+    command: &AeroCloudV7Command,
+) -> eyre::Result<()> {
+    match command {
+        AeroCloudV7Command::ListProjects { status } => {
+            self::v7::list_projects::run(args, client, cache, *status).await
+        }
+        AeroCloudV7Command::CreateProject { name, description } => {
+            self::v7::create_project::run(
+                args,
+                client,
+                name,
+                description.as_deref(),
+            )
+            .await
+        }
+        AeroCloudV7Command::DeleteProjects { project_ids } => {
+            self::v7::delete_projects::run(args, client, cache, project_ids).await
+        }
+        AeroCloudV7Command::ListSimulations {
+            project_id,
+            show_results,
+            status,
+            fluid_speed,
+            quality,
+            yaw_angle,
+            filter,
+            units,
+            derived,
+            at_speed,
+        } => {
+            self::v7::list_simulations::run(
+                args,
+                client,
+                cache,
+                config.project_id_or_fail(project_id.as_ref())?,
+                *show_results,
+                &self::v7::list_simulations::Query {
+                    status: *status,
+                    quality: *quality,
+                    fluid_speed: fluid_speed.clone(),
+                    yaw_angle: yaw_angle.clone(),
+                },
+                filter.as_ref(),
+                &Processing {
+                    units: *units,
+                    derived: derived.clone(),
+                    at_speed: *at_speed,
+                },
+            )
+            .await
+        }
+        AeroCloudV7Command::GetSimulation {
+            simulation_id,
+            units,
+        } => {
+            self::v7::get_simulation::run(args, client, simulation_id, *units)
                 .await
-            }
-            AeroCloudV7Command::CreateSimulation {
-                params,
-                project_id,
-                model_id,
-            } => {
-                self::v7::create_simulation::run(
-                    args,
-                    &client,
-                    model_id.clone(),
-                    project_id.clone(),
-                    &params
-                        .clone()
-                        .contents()
-                        .wrap_err("failed to read contents")?,
-                )
+        }
+        AeroCloudV7Command::ListReusableModels => {
+            self::v7::list_reusable_models::run(args, client, cache).await
+        }
+        AeroCloudV7Command::CreateModel { params } => {
+            self::v7::create_model::run(
+                args,
+                client,
+                params.is_file().then(|| Path::new(params.filename())),
+                &params
+                    .clone()
+                    .contents()
+                    .wrap_err("failed to read contents")?,
+            )
+            .await
+        }
+        AeroCloudV7Command::CreateSimulation {
+            params,
+            project_id,
+            model_id,
+            git_revision,
+        } => {
+            self::v7::create_simulation::run(
+                args,
+                config,
+                client,
+                model_id.clone(),
+                project_id.clone(),
+                params.is_file().then(|| Path::new(params.filename())),
+                &params
+                    .clone()
+                    .contents()
+                    .wrap_err("failed to read contents")?,
+                git_revision
+                    .as_deref()
+                    .or(config.project.git_revision.as_deref()),
+            )
+            .await
+        }
+        AeroCloudV7Command::DeleteSimulations { simulation_ids } => {
+            self::v7::delete_simulations::run(args, client, cache, simulation_ids)
                 .await
+        }
+        AeroCloudV7Command::WaitForSimulations {
+            ids,
+            project,
+            revision,
+            created_after,
+            interval_secs,
+            timeout_secs,
+        } => {
+            self::v7::wait_for_simulations::run(
+                args,
+                client,
+                &Notifiers::new(args, config)?,
+                ids,
+                project.clone().map(|project_id| Discovery {
+                    project_id,
+                    revision: revision.clone(),
+                    created_after: *created_after,
+                }),
+                Duration::from_secs(*interval_secs),
+                timeout_secs.map(Duration::from_secs),
+            )
+            .await
+        }
+        AeroCloudV7Command::Watch {
+            ids,
+            project,
+            revision,
+            created_after,
+            interval_secs,
+        } => {
+            self::v7::watch::run(
+                args,
+                client,
+                ids,
+                project.clone().map(|project_id| Discovery {
+                    project_id,
+                    revision: revision.clone(),
+                    created_after: *created_after,
+                }),
+                Duration::from_secs(*interval_secs),
+            )
+            .await
+        }
+        AeroCloudV7Command::Plot {
+            simulation_ids,
+            metric,
+            units,
+            height,
+        } => {
+            self::v7::plot::run(
+                args,
+                client,
+                simulation_ids,
+                *metric,
+                *units,
+                *height,
+            )
+            .await
+        }
+        AeroCloudV7Command::Report {
+            project_id,
+            format,
+            metrics,
+            units,
+            derived,
+            at_speed,
+            output,
+        } => {
+            self::v7::report::run(
+                args,
+                client,
+                cache,
+                config.project_id_or_fail(project_id.as_ref())?,
+                *format,
+                metrics,
+                &Processing {
+                    units: *units,
+                    derived: derived.clone(),
+                    at_speed: *at_speed,
+                },
+                output.as_deref(),
+            )
+            .await
+        }
+        AeroCloudV7Command::Batch {
+            root_dir,
+            recursive,
+            marker,
+            include,
+            exclude,
+            git_revision,
+        } => {
+            if args.debug && args.log_to_path.is_none() {
+                eyre::bail!(
+                    "must log to file, otherwise the UI would get corrupted by logs"
+                );
             }
-            AeroCloudV7Command::DeleteSimulations { simulation_ids } => {
-                self::v7::delete_simulations::run(args, &client, simulation_ids)
-                    .await
-            }
-            AeroCloudV7Command::WaitForSimulations { ids } => {
-                self::v7::wait_for_simulations::run(args, &client, ids).await
-            }
-            AeroCloudV7Command::Batch { root_dir } => {
-                if args.debug && args.log_to_path.is_none() {
-                    eyre::bail!(
-                        "must log to file, otherwise the UI would get corrupted by logs"
-                    );
-                }
 
-                self::v7::batch::run(
-                    &client,
-                    root_dir.as_ref().map(PathBuf::as_path),
-                )
-                .await
-            }
-        },
+            self::v7::batch::run(
+                client,
+                cache.clone(),
+                Notifiers::new(args, config)?,
+                args.daemon_addr,
+                ParamsFill {
+                    defaults: config.project.simulation.clone(),
+                    git_revision: git_revision
+                        .clone()
+                        .or_else(|| config.project.git_revision.clone()),
+                },
+                root_dir
+                    .as_ref()
+                    .or(config.project.batch_root.as_ref())
+                    .map(|root_dir| self::v7::batch::Discovery {
+                        recursive: *recursive,
+                        marker: marker.clone(),
+                        include: include.clone(),
+                        exclude: exclude.clone(),
+                        ..self::v7::batch::Discovery::new(root_dir.clone())
+                    }),
+            )
+            .await
+        }
     }
 }
@@ -14,7 +14,7 @@ pub async fn run(
 ) -> eyre::Result<()> {
     info!("validating token...");
 
-    config.aerocloud_token = Some(token.to_owned());
+    config.set_aerocloud_token(token.to_owned());
 
     let client =
         build_aerocloud_client_from_config(&config, &args.http_timeout())?;
@@ -1,9 +1,14 @@
 use crate::{
     aerocloud::{
         Client,
-        types::{ProjectV7, SimulationV7},
+        cache::Cache,
+        fmt::human_simulation_status,
+        poll,
+        types::{Id, ProjectV7, SimulationStatus, SimulationV7},
     },
     commands::aerocloud::v7::batch::{
+        list_view::{ListView, Selection, StateFilter},
+        params_form::{FormAction, ParamsForm},
         project_picker::{
             ProjectPicker, ProjectPickerState, refresh_projects_in_background,
         },
@@ -11,7 +16,10 @@ use crate::{
         simulation_params::{SimulationParams, SubmissionState},
         submit::submit_batch_in_background,
     },
+    daemon,
     fmt::human_err_report,
+    notify::{Notification, Notifiers},
+    params_file::ParamsFormat,
 };
 use bytesize::ByteSize;
 use color_eyre::eyre::{self, WrapErr};
@@ -37,17 +45,25 @@ use ratatui::{
 use std::{
     borrow::Cow,
     mem,
+    net::SocketAddr,
     path::{Path, PathBuf},
     time::Duration,
 };
-use tokio::{sync::mpsc, time};
+use tokio::{sync::mpsc, task::JoinSet, time};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+mod discovery;
+mod list_view;
+mod params_form;
 mod project_picker;
 mod simulation_detail;
 mod simulation_params;
 mod submit;
+mod watcher;
+
+pub use discovery::Discovery;
+pub use simulation_params::ParamsFill;
 
 // Made using https://budavariam.github.io/asciiart-text/multi variant `ANSI Shadow`
 const LOGO_ASCII_ART: &str = include_str!("../../../aerocloud/logo.txt");
@@ -64,12 +80,25 @@ const MIN_TERM_SIZE: Size = Size::new(110, 38);
 
 const SLEEP_FOR_FEEDBACK: Duration = Duration::from_millis(100);
 
-pub async fn run(client: &Client, root_dir: Option<&Path>) -> eyre::Result<()> {
-    let sims = if let Some(root_dir) = root_dir {
-        let sims = SimulationParams::many_from_root_dir(client, root_dir).await?;
+/// How often sent simulations are checked for progress.
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+pub async fn run(
+    client: &Client,
+    cache: Cache,
+    notifiers: Notifiers,
+    daemon_addr: SocketAddr,
+    fill: ParamsFill,
+    discovery: Option<Discovery>,
+) -> eyre::Result<()> {
+    let sims = if let Some(discovery) = &discovery {
+        let sims = SimulationParams::discover(client, &fill, discovery).await?;
 
         if sims.is_empty() {
-            tracing::error!("no simulations found in `{}`", root_dir.display());
+            tracing::error!(
+                "no simulations found in `{}`",
+                discovery.root_dir.display()
+            );
 
             return Ok(());
         }
@@ -79,30 +108,143 @@ pub async fn run(client: &Client, root_dir: Option<&Path>) -> eyre::Result<()> {
         vec![]
     };
 
-    let mut app =
-        Batch::new(client.clone(), root_dir.map(ToOwned::to_owned), sims);
+    let mut app = Batch::new(
+        client.clone(),
+        cache,
+        notifiers,
+        daemon_addr,
+        fill,
+        discovery,
+        sims,
+    );
 
     let mut terminal = ratatui::init();
     let result = app.run(&mut terminal).await;
 
     ratatui::restore();
 
+    if !app.background.is_empty() {
+        tracing::info!("waiting for notifications to be delivered...");
+        app.background.join_all().await;
+    }
+
     result
 }
 
+/// Summary of a finished batch submission, of which `sent` out of `count`
+/// simulations made it.
+fn batch_notification(
+    project: &ProjectV7,
+    sent: Vec<SimulationV7>,
+    count: usize,
+) -> Notification {
+    let failed = count - sent.len();
+
+    Notification {
+        title: "Batch submitted".into(),
+        message: if failed == 0 {
+            format!(
+                "{count} simulation(s) submitted to project `{}`: {}",
+                project.name, project.browser_url
+            )
+        } else {
+            format!(
+                "{} of {count} simulation(s) submitted to project `{}`, {failed} failed: {}",
+                sent.len(),
+                project.name,
+                project.browser_url
+            )
+        },
+        simulations: sent,
+    }
+}
+
+/// Whether a bulk reset would change the submission state of `sim`.
+fn is_resettable(sim: &SimulationParams) -> bool {
+    sim.selected && !matches!(sim.submission_state, SubmissionState::Ready)
+}
+
+/// Reloads the simulations affected by changes to `paths`, picking up new ones
+/// and dropping those gone, out of the ones in `known_dirs`.
+fn reload_changed_in_background(
+    client: Client,
+    fill: ParamsFill,
+    discovery: Discovery,
+    known_dirs: Vec<PathBuf>,
+    paths: Vec<PathBuf>,
+    tx: mpsc::Sender<Event>,
+) {
+    tokio::spawn(async move {
+        let sim_dirs = match discovery.sim_dirs().await {
+            Ok(sim_dirs) => sim_dirs,
+            Err(err) => {
+                tracing::warn!("failed to discover simulations: {err:?}");
+                return Ok(());
+            }
+        };
+
+        let mut reloaded: Vec<_> = known_dirs
+            .iter()
+            .filter(|dir| !sim_dirs.contains(dir))
+            .map(|dir| (dir.clone(), None))
+            .collect();
+
+        for dir in sim_dirs {
+            // NOTE: defaults apply to every simulation nested where they are.
+            if known_dirs.contains(&dir)
+                && !paths.iter().any(|path| {
+                    path.starts_with(&dir)
+                        || (path.file_stem()
+                            == Some(SimulationParams::DEFAULTS_STEM.as_ref())
+                            && ParamsFormat::from_path(path).is_some()
+                            && path
+                                .parent()
+                                .is_some_and(|parent| dir.starts_with(parent)))
+                })
+            {
+                continue;
+            }
+
+            let res =
+                SimulationParams::from_dir(&client, &fill, &discovery, &dir)
+                    .await;
+
+            reloaded.push((dir, Some(res)));
+        }
+
+        tx.send(Event::SimDirsReloaded(reloaded)).await?;
+
+        Ok::<(), eyre::Report>(())
+    });
+}
+
+/// Periodically asks for sent simulations to be polled, until the UI is gone.
+async fn tick_poll_sent(tx: mpsc::Sender<Event>) {
+    let mut interval = time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if tx.send(Event::PollSent).await.is_err() {
+            break;
+        }
+    }
+}
+
 pub fn refresh_sims_in_background(
     client: Client,
-    root_dir: &Path,
+    fill: ParamsFill,
+    discovery: &Discovery,
     tx: mpsc::Sender<Event>,
 ) {
-    let root_dir = root_dir.to_owned();
+    let discovery = discovery.clone();
 
     tokio::spawn(async move {
         // NOTE: sleep so that reloading popup is shown and user has visual feedback on the
         // operation.
         time::sleep(SLEEP_FOR_FEEDBACK).await;
 
-        let res = SimulationParams::many_from_root_dir(&client, &root_dir).await;
+        let res = SimulationParams::discover(&client, &fill, &discovery).await;
         tx.send(Event::SimsReloaded(res)).await?;
 
         Ok::<(), eyre::Report>(())
@@ -112,11 +254,20 @@ pub fn refresh_sims_in_background(
 #[derive(Debug)]
 struct Batch {
     client: Client,
+    cache: Cache,
+    notifiers: Notifiers,
+    daemon_addr: SocketAddr,
+    fill: ParamsFill,
     running: bool,
+    /// Notifications and daemon registrations still being delivered, awaited
+    /// before exiting.
+    background: JoinSet<()>,
     term_size: Size,
 
-    root_dir: Option<PathBuf>,
+    discovery: Option<Discovery>,
     simulations: Vec<SimulationParams>,
+    view: ListView,
+    is_polling: bool,
 
     state: State,
 }
@@ -126,10 +277,26 @@ enum ActiveState {
     #[default]
     ViewingList,
     ViewingDetail,
+    Searching,
+    EditingParams {
+        form: Box<ParamsForm>,
+        prev: Box<Self>,
+    },
+    PickingParamsFile {
+        paths: Vec<PathBuf>,
+        list_state: ListState,
+        prev: Box<Self>,
+    },
+    /// The UI is suspended while `path` is open in an external editor.
+    EditingExternally {
+        path: PathBuf,
+        prev: Box<Self>,
+    },
     ConfirmExit {
         prev: Box<Self>,
     },
     ConfirmSubmit,
+    ConfirmReset,
     ReloadingSims,
     ReloadingSimsFailed(String),
     Submitting {
@@ -138,6 +305,8 @@ enum ActiveState {
         bytes_progress: ByteSize,
         sims_count: usize,
         sims_progress: usize,
+        /// Simulations sent so far, notified about once all are done.
+        sent: Vec<SimulationV7>,
     },
 }
 
@@ -168,6 +337,11 @@ pub enum Event {
         internal_id: Uuid,
         res: eyre::Result<Box<SimulationV7>, eyre::Report>,
     },
+    PollSent,
+    SentPolled(eyre::Result<Vec<eyre::Result<SimulationV7>>>),
+    FilesChanged(Vec<PathBuf>),
+    /// `None` for dirs that no longer exist.
+    SimDirsReloaded(Vec<(PathBuf, Option<eyre::Result<SimulationParams>>)>),
     Exit,
 }
 
@@ -194,16 +368,30 @@ async fn handle_term_events(tx: mpsc::Sender<Event>) -> eyre::Result<()> {
 impl Batch {
     fn new(
         client: Client,
-        root_dir: Option<PathBuf>,
+        cache: Cache,
+        notifiers: Notifiers,
+        daemon_addr: SocketAddr,
+        fill: ParamsFill,
+        discovery: Option<Discovery>,
         simulations: Vec<SimulationParams>,
     ) -> Self {
+        let mut view = ListView::default();
+        view.refresh(&simulations);
+
         Self {
             state: State::Init,
             running: false,
             term_size: Size::default(),
-            root_dir,
+            discovery,
             simulations,
+            view,
+            is_polling: false,
             client,
+            cache,
+            notifiers,
+            daemon_addr,
+            fill,
+            background: JoinSet::new(),
         }
     }
 
@@ -213,10 +401,24 @@ impl Batch {
 
         let (event_tx, mut event_rx) = mpsc::channel(10);
 
-        tokio::spawn(handle_term_events(event_tx.clone()));
+        let mut term_events = tokio::spawn(handle_term_events(event_tx.clone()));
+
+        tokio::spawn(tick_poll_sent(event_tx.clone()));
+
+        let _watcher = self.discovery.as_ref().and_then(|discovery| {
+            watcher::watch_root_dir(&discovery.root_dir, event_tx.clone())
+                .inspect_err(|err| {
+                    tracing::warn!("not reloading on changes: {err:?}");
+                })
+                .ok()
+        });
 
         if let State::Init = self.state {
-            refresh_projects_in_background(self.client.clone(), event_tx.clone());
+            refresh_projects_in_background(
+                self.client.clone(),
+                self.cache.clone(),
+                event_tx.clone(),
+            );
 
             self.state = State::PickingProject {
                 state: ProjectPickerState::default(),
@@ -233,6 +435,31 @@ impl Batch {
 
             self.handle_event(event, event_tx.clone()).await?;
 
+            if let Some(path) = self.external_edit() {
+                let res = match self.prepare_external_edit(&path).await {
+                    Ok(()) => {
+                        // NOTE: terminal events must not be read while the
+                        // editor runs, otherwise keys would be stolen from it.
+                        term_events.abort();
+                        let _ = term_events.await;
+
+                        ratatui::restore();
+                        let res = open_in_editor(&path).await;
+                        *terminal = ratatui::init();
+
+                        term_events =
+                            tokio::spawn(handle_term_events(event_tx.clone()));
+
+                        res
+                    }
+                    Err(err) => Err(err),
+                };
+
+                self.finish_external_edit(res).await;
+            }
+
+            self.refresh_view();
+
             terminal.draw(|frame| self.draw(frame))?;
         }
 
@@ -258,6 +485,35 @@ impl Batch {
             return Ok(());
         }
 
+        if let Event::PollSent = event {
+            self.poll_sent(tx);
+            return Ok(());
+        }
+
+        if let Event::SentPolled(res) = event {
+            self.update_sent(res);
+            return Ok(());
+        }
+
+        if let Event::FilesChanged(paths) = event {
+            if let Some(discovery) = &self.discovery {
+                reload_changed_in_background(
+                    self.client.clone(),
+                    self.fill.clone(),
+                    discovery.clone(),
+                    self.simulations.iter().map(|sim| sim.dir.clone()).collect(),
+                    paths,
+                    tx,
+                );
+            }
+            return Ok(());
+        }
+
+        if let Event::SimDirsReloaded(reloaded) = event {
+            self.update_dirs(reloaded);
+            return Ok(());
+        }
+
         match self.state {
             State::Init => {}
             State::PickingProject { ref mut state } => {
@@ -270,7 +526,14 @@ impl Batch {
                         sim_detail_scrollbar_state: ScrollbarState::default(),
                     };
                 } else {
-                    state.handle_event(event, self.client.clone(), tx).await?;
+                    state
+                        .handle_event(
+                            event,
+                            self.client.clone(),
+                            self.cache.clone(),
+                            tx,
+                        )
+                        .await?;
                 }
             }
             State::Active { .. } => {
@@ -305,13 +568,15 @@ impl Batch {
             (ActiveState::ViewingList, Event::KeyPressed(key_event)) => {
                 match (key_event.code, key_event.modifiers) {
                     (KeyCode::Char(' '), _)
-                        if let Some(idx) = sims_list_state.selected()
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state)
                             && let Some(sim) = self.simulations.get_mut(idx) =>
                     {
                         sim.selected = !sim.selected;
                     }
                     (KeyCode::Char('r'), KeyModifiers::CONTROL)
-                        if let Some(idx) = sims_list_state.selected()
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state)
                             && let Some(sim) = self.simulations.get_mut(idx)
                             && let Err(err) =
                                 sim.reset_submission_state().await =>
@@ -323,17 +588,19 @@ impl Batch {
                     }
                     (KeyCode::Char('o'), KeyModifiers::CONTROL)
                         if self
-                            .simulations
-                            .iter()
-                            .any(SimulationParams::is_submittable) =>
+                            .view
+                            .submittable(&self.simulations)
+                            .next()
+                            .is_some() =>
                     {
                         next_state = Some(ActiveState::ConfirmSubmit);
                     }
                     (KeyCode::Char('r'), _) => {
-                        if let Some(root_dir) = self.root_dir.as_ref() {
+                        if let Some(discovery) = &self.discovery {
                             refresh_sims_in_background(
                                 self.client.clone(),
-                                root_dir,
+                                self.fill.clone(),
+                                discovery,
                                 tx.clone(),
                             );
                         }
@@ -346,6 +613,32 @@ impl Batch {
                             prev: Box::new(ActiveState::ViewingList),
                         });
                     }
+                    // NOTE: selects both the item left and the one reached, so
+                    // holding shift selects the whole range.
+                    (KeyCode::Up | KeyCode::Down, KeyModifiers::SHIFT) => {
+                        if let Some(idx) = self.view.selected(sims_list_state) {
+                            self.simulations[idx].selected = true;
+                        }
+
+                        if key_event.code == KeyCode::Up {
+                            sims_list_state.select_previous();
+                        } else {
+                            sims_list_state.select_next();
+                        }
+
+                        // NOTE: `select_next` may go past the end until the
+                        // next refresh clamps it.
+                        let visible = self.view.visible();
+                        let idx = sims_list_state
+                            .selected()
+                            .and_then(|idx| visible.get(idx).or(visible.last()));
+
+                        if let Some(&idx) = idx {
+                            self.simulations[idx].selected = true;
+                        }
+
+                        sim_detail_scrollbar_state.first();
+                    }
                     (KeyCode::Up, _) => {
                         sims_list_state.select_previous();
                         sim_detail_scrollbar_state.first();
@@ -354,6 +647,78 @@ impl Batch {
                         sims_list_state.select_next();
                         sim_detail_scrollbar_state.first();
                     }
+                    (KeyCode::Char('e'), _)
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state)
+                            && let Some(sim) = self.simulations.get(idx) =>
+                    {
+                        next_state = Some(ActiveState::EditingParams {
+                            form: Box::new(ParamsForm::new(sim)),
+                            prev: Box::new(ActiveState::ViewingList),
+                        });
+                    }
+                    (KeyCode::Char('E'), _)
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state)
+                            && let Some(sim) = self.simulations.get(idx) =>
+                    {
+                        let paths: Vec<PathBuf> =
+                            ParamsForm::new(sim).paths().cloned().collect();
+                        let prev = Box::new(ActiveState::ViewingList);
+
+                        next_state = Some(if let [path] = paths.as_slice() {
+                            ActiveState::EditingExternally {
+                                path: path.clone(),
+                                prev,
+                            }
+                        } else {
+                            ActiveState::PickingParamsFile {
+                                paths,
+                                list_state: ListState::default()
+                                    .with_selected(Some(0)),
+                                prev,
+                            }
+                        });
+                    }
+                    (KeyCode::Char('/'), _) => {
+                        next_state = Some(ActiveState::Searching);
+                    }
+                    (KeyCode::Char('f'), _) => {
+                        self.view.state_filter =
+                            StateFilter::next(self.view.state_filter);
+                        sims_list_state.select_first();
+                    }
+                    (KeyCode::Char('s'), _) => {
+                        self.view.sort = self.view.sort.next();
+                    }
+                    (KeyCode::Char('a'), _) => {
+                        self.view.select(&mut self.simulations, Selection::All);
+                    }
+                    (KeyCode::Char('n'), _) => {
+                        self.view.select(&mut self.simulations, Selection::None);
+                    }
+                    (KeyCode::Char('i'), _) => {
+                        self.view
+                            .select(&mut self.simulations, Selection::Invert);
+                    }
+                    (KeyCode::Char('S'), _)
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state) =>
+                    {
+                        let filter = StateFilter::of(
+                            &self.simulations[idx].submission_state,
+                        );
+
+                        self.view.select(
+                            &mut self.simulations,
+                            Selection::Only(filter),
+                        );
+                    }
+                    (KeyCode::Char('R'), _)
+                        if self.simulations.iter().any(is_resettable) =>
+                    {
+                        next_state = Some(ActiveState::ConfirmReset);
+                    }
                     (KeyCode::Tab, _) => {
                         next_state = Some(ActiveState::ViewingDetail);
                     }
@@ -363,13 +728,15 @@ impl Batch {
             (ActiveState::ViewingDetail, Event::KeyPressed(key_event)) => {
                 match (key_event.code, key_event.modifiers) {
                     (KeyCode::Char(' '), _)
-                        if let Some(idx) = sims_list_state.selected()
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state)
                             && let Some(sim) = self.simulations.get_mut(idx) =>
                     {
                         sim.selected = !sim.selected;
                     }
                     (KeyCode::Char('r'), KeyModifiers::CONTROL)
-                        if let Some(idx) = sims_list_state.selected()
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state)
                             && let Some(sim) = self.simulations.get_mut(idx)
                             && let Err(err) =
                                 sim.reset_submission_state().await =>
@@ -380,10 +747,11 @@ impl Batch {
                         );
                     }
                     (KeyCode::Char('r'), _) => {
-                        if let Some(root_dir) = self.root_dir.as_ref() {
+                        if let Some(discovery) = &self.discovery {
                             refresh_sims_in_background(
                                 self.client.clone(),
-                                root_dir,
+                                self.fill.clone(),
+                                discovery,
                                 tx.clone(),
                             );
                         }
@@ -392,9 +760,10 @@ impl Batch {
                     }
                     (KeyCode::Char('o'), KeyModifiers::CONTROL)
                         if self
-                            .simulations
-                            .iter()
-                            .any(SimulationParams::is_submittable) =>
+                            .view
+                            .submittable(&self.simulations)
+                            .next()
+                            .is_some() =>
                     {
                         next_state = Some(ActiveState::ConfirmSubmit);
                     }
@@ -418,12 +787,121 @@ impl Batch {
                     (KeyCode::Down, _) => {
                         sim_detail_scrollbar_state.next();
                     }
+                    (KeyCode::Char('e'), _)
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state)
+                            && let Some(sim) = self.simulations.get(idx) =>
+                    {
+                        next_state = Some(ActiveState::EditingParams {
+                            form: Box::new(ParamsForm::new(sim)),
+                            prev: Box::new(ActiveState::ViewingDetail),
+                        });
+                    }
+                    (KeyCode::Char('E'), _)
+                        if let Some(idx) =
+                            self.view.selected(sims_list_state)
+                            && let Some(sim) = self.simulations.get(idx) =>
+                    {
+                        let paths: Vec<PathBuf> =
+                            ParamsForm::new(sim).paths().cloned().collect();
+                        let prev = Box::new(ActiveState::ViewingDetail);
+
+                        next_state = Some(if let [path] = paths.as_slice() {
+                            ActiveState::EditingExternally {
+                                path: path.clone(),
+                                prev,
+                            }
+                        } else {
+                            ActiveState::PickingParamsFile {
+                                paths,
+                                list_state: ListState::default()
+                                    .with_selected(Some(0)),
+                                prev,
+                            }
+                        });
+                    }
                     (KeyCode::Tab, _) => {
                         next_state = Some(ActiveState::ViewingList);
                     }
                     _ => {}
                 }
             }
+            (
+                ActiveState::EditingParams { form, prev },
+                Event::KeyPressed(key_event),
+            ) => match form.handle_key(key_event) {
+                FormAction::Continue => {}
+                FormAction::Cancel => {
+                    next_state = Some(*prev.clone());
+                }
+                FormAction::Save => {
+                    if let Some(idx) = self.view.selected(sims_list_state)
+                        && let Some(sim) = self.simulations.get_mut(idx)
+                        && let Some(discovery) = &self.discovery
+                    {
+                        match save_params(
+                            &self.client,
+                            &self.fill,
+                            discovery,
+                            form,
+                            sim,
+                        )
+                        .await
+                        {
+                            Ok(()) => {
+                                next_state = Some(*prev.clone());
+                            }
+                            Err(err) => form.set_error(human_err_report(&err)),
+                        }
+                    }
+                }
+            },
+            (
+                ActiveState::PickingParamsFile {
+                    paths,
+                    list_state,
+                    prev,
+                },
+                Event::KeyPressed(key_event),
+            ) => match key_event.code {
+                KeyCode::Up => list_state.select_previous(),
+                KeyCode::Down => list_state.select_next(),
+                KeyCode::Enter
+                    if let Some(path) =
+                        list_state.selected().and_then(|idx| paths.get(idx)) =>
+                {
+                    next_state = Some(ActiveState::EditingExternally {
+                        path: path.clone(),
+                        prev: prev.clone(),
+                    });
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    next_state = Some(*prev.clone());
+                }
+                _ => {}
+            },
+            (ActiveState::Searching, Event::KeyPressed(key_event)) => {
+                match key_event.code {
+                    KeyCode::Enter => {
+                        next_state = Some(ActiveState::ViewingList);
+                    }
+                    KeyCode::Esc => {
+                        self.view.query.clear();
+                        next_state = Some(ActiveState::ViewingList);
+                    }
+                    KeyCode::Backspace => {
+                        self.view.query.pop();
+                        sims_list_state.select_first();
+                    }
+                    KeyCode::Char(c) => {
+                        self.view.query.push(c);
+                        sims_list_state.select_first();
+                    }
+                    KeyCode::Up => sims_list_state.select_previous(),
+                    KeyCode::Down => sims_list_state.select_next(),
+                    _ => {}
+                }
+            }
             (ActiveState::ConfirmExit { prev }, Event::KeyPressed(key_event)) => {
                 match key_event.code {
                     KeyCode::Char('y') => {
@@ -441,9 +919,8 @@ impl Batch {
                 match key_event.code {
                     KeyCode::Char('y') => {
                         let sims_to_submit: Vec<SimulationParams> = self
-                            .simulations
-                            .iter()
-                            .filter(|sim_params| sim_params.is_submittable())
+                            .view
+                            .submittable(&self.simulations)
                             .cloned()
                             .collect();
 
@@ -471,6 +948,7 @@ impl Batch {
                             sims_count,
                             bytes_progress: ByteSize::default(),
                             bytes_count,
+                            sent: vec![],
                         });
                     }
                     KeyCode::Char('n') => {
@@ -479,6 +957,30 @@ impl Batch {
                     _ => {}
                 }
             }
+            (ActiveState::ConfirmReset, Event::KeyPressed(key_event)) => {
+                match key_event.code {
+                    KeyCode::Char('y') => {
+                        for sim in self
+                            .simulations
+                            .iter_mut()
+                            .filter(|sim| is_resettable(sim))
+                        {
+                            if let Err(err) = sim.reset_submission_state().await {
+                                tracing::error!(
+                                    "failed to flush submission state for sim in dir `{}`: {err:?}",
+                                    sim.dir.display()
+                                );
+                            }
+                        }
+
+                        next_state = Some(ActiveState::ViewingList);
+                    }
+                    KeyCode::Char('n') => {
+                        next_state = Some(ActiveState::ViewingList);
+                    }
+                    _ => {}
+                }
+            }
             (ActiveState::ReloadingSims, Event::KeyPressed(key_event))
                 if key_event.code == KeyCode::Char('q') =>
             {
@@ -488,13 +990,18 @@ impl Batch {
                 ActiveState::ReloadingSims,
                 Event::SimsReloaded(Ok(mut simulations)),
             ) => {
-                // Copy over selection status.
+                // Copy over selection status and what was polled.
                 for new_sim in &mut simulations {
-                    new_sim.selected = self
+                    let prev = self
                         .simulations
                         .iter()
-                        .find(|sim| sim.dir == new_sim.dir)
-                        .is_none_or(|sim| sim.selected);
+                        .find(|sim| sim.dir == new_sim.dir);
+
+                    new_sim.selected = prev.is_none_or(|sim| sim.selected);
+
+                    if let Some(prev) = prev {
+                        new_sim.keep_remote(prev);
+                    }
                 }
 
                 self.simulations = simulations;
@@ -532,6 +1039,7 @@ impl Batch {
                 ActiveState::Submitting {
                     sims_progress,
                     sims_count,
+                    sent,
                     ..
                 },
                 Event::SimSubmitted { internal_id, res },
@@ -542,10 +1050,22 @@ impl Batch {
                     .find(|sim_params| sim_params.internal_id == internal_id)
                 {
                     let state = match res {
-                        Ok(sim) => SubmissionState::Sent {
-                            id: sim.id.clone(),
-                            browser_url: sim.browser_url.clone(),
-                        },
+                        Ok(sim) => {
+                            let daemon_addr = self.daemon_addr;
+                            let id = sim.id.clone();
+
+                            self.background.spawn(async move {
+                                daemon::track_if_running(daemon_addr, vec![id])
+                                    .await;
+                            });
+
+                            sent.push((*sim).clone());
+
+                            SubmissionState::Sent {
+                                id: sim.id.clone(),
+                                browser_url: sim.browser_url.clone(),
+                            }
+                        }
                         Err(err) => {
                             SubmissionState::Error(human_err_report(&err))
                         }
@@ -559,6 +1079,17 @@ impl Batch {
                     *sims_progress += 1;
 
                     if sims_progress >= sims_count {
+                        let notifiers = self.notifiers.clone();
+                        let notification = batch_notification(
+                            project,
+                            mem::take(sent),
+                            *sims_count,
+                        );
+
+                        self.background.spawn(async move {
+                            notifiers.send(&notification).await;
+                        });
+
                         time::sleep(SLEEP_FOR_FEEDBACK * 3).await;
 
                         next_state = Some(ActiveState::ViewingList);
@@ -581,6 +1112,227 @@ impl Batch {
         self.running = false;
     }
 
+    /// Fetches sent simulations that are not done yet, unless still busy with
+    /// the previous round.
+    fn poll_sent(&mut self, tx: mpsc::Sender<Event>) {
+        if self.is_polling {
+            return;
+        }
+
+        let ids: Vec<Id> = self
+            .simulations
+            .iter()
+            .filter(|sim| sim.needs_polling())
+            .filter_map(SimulationParams::sent_id)
+            .cloned()
+            .collect();
+
+        if ids.is_empty() {
+            return;
+        }
+
+        self.is_polling = true;
+
+        let client = self.client.clone();
+
+        tokio::spawn(async move {
+            let res = poll::fetch_all(&client, ids).await;
+            tx.send(Event::SentPolled(res)).await?;
+
+            Ok::<(), eyre::Report>(())
+        });
+    }
+
+    fn update_sent(
+        &mut self,
+        res: eyre::Result<Vec<eyre::Result<SimulationV7>>>,
+    ) {
+        self.is_polling = false;
+
+        let fetched = match res {
+            Ok(fetched) => fetched,
+            Err(err) => {
+                tracing::warn!("failed to poll sent simulations: {err:?}");
+                return;
+            }
+        };
+
+        for remote in fetched {
+            match remote {
+                Ok(remote) => {
+                    if let Some(sim) = self.simulations.iter_mut().find(|sim| {
+                        sim.sent_id().is_some_and(|id| id.0 == remote.id.0)
+                    }) {
+                        sim.remote = Some(Box::new(remote));
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("failed to poll sent simulation: {err:?}");
+                }
+            }
+        }
+    }
+
+    fn external_edit(&self) -> Option<PathBuf> {
+        if let State::Active {
+            state: ActiveState::EditingExternally { ref path, .. },
+            ..
+        } = self.state
+        {
+            Some(path.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Replaces simulations reloaded after their dir changed, keeping what
+    /// only lives in memory.
+    fn update_dirs(
+        &mut self,
+        reloaded: Vec<(PathBuf, Option<eyre::Result<SimulationParams>>)>,
+    ) {
+        for (dir, res) in reloaded {
+            let idx = self.simulations.iter().position(|sim| sim.dir == dir);
+
+            // NOTE: the in-memory state of a sim being uploaded must not be
+            // replaced with the one on disk.
+            if let Some(idx) = idx
+                && matches!(
+                    self.simulations[idx].submission_state,
+                    SubmissionState::Sending
+                )
+            {
+                continue;
+            }
+
+            match (idx, res) {
+                (Some(idx), None) => {
+                    self.simulations.remove(idx);
+                }
+                (Some(idx), Some(Ok(mut new_sim))) => {
+                    let prev = &self.simulations[idx];
+
+                    new_sim.internal_id = prev.internal_id;
+                    new_sim.selected = prev.selected;
+                    new_sim.keep_remote(prev);
+
+                    self.simulations[idx] = new_sim;
+                }
+                (None, Some(Ok(new_sim))) => {
+                    self.simulations.push(new_sim);
+                }
+                (Some(idx), Some(Err(err))) => {
+                    self.simulations[idx].reload_error =
+                        Some(human_err_report(&err));
+                }
+                (None, Some(Err(err))) => {
+                    tracing::warn!(
+                        "failed to load simulation in dir `{}`: {err:?}",
+                        dir.display()
+                    );
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    /// Recomputes the shown simulations, keeping the cursor on the same one
+    /// when still shown, otherwise within them.
+    fn refresh_view(&mut self) {
+        let State::Active {
+            ref mut sims_list_state,
+            ..
+        } = self.state
+        else {
+            self.view.refresh(&self.simulations);
+            return;
+        };
+
+        let under_cursor = self
+            .view
+            .selected(sims_list_state)
+            .and_then(|idx| self.simulations.get(idx))
+            .map(|sim| sim.internal_id);
+
+        self.view.refresh(&self.simulations);
+
+        if let Some(pos) = under_cursor.and_then(|internal_id| {
+            self.view
+                .visible()
+                .iter()
+                .position(|&idx| self.simulations[idx].internal_id == internal_id)
+        }) {
+            sims_list_state.select(Some(pos));
+        }
+
+        let len = self.view.visible().len();
+
+        match sims_list_state.selected() {
+            _ if len == 0 => sims_list_state.select(None),
+            Some(idx) if idx >= len => sims_list_state.select(Some(len - 1)),
+            None => sims_list_state.select(Some(0)),
+            Some(_) => {}
+        }
+    }
+
+    fn selected_sim(&self) -> Option<&SimulationParams> {
+        let State::Active {
+            ref sims_list_state,
+            ..
+        } = self.state
+        else {
+            return None;
+        };
+
+        self.view
+            .selected(sims_list_state)
+            .and_then(|idx| self.simulations.get(idx))
+    }
+
+    /// Creates the file to edit from current params when missing.
+    async fn prepare_external_edit(&self, path: &Path) -> eyre::Result<()> {
+        if let Some(sim) = self.selected_sim() {
+            ParamsForm::new(sim).ensure_exists(path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Reparses the simulation edited externally, showing errors in a popup.
+    async fn finish_external_edit(&mut self, res: eyre::Result<()>) {
+        let State::Active {
+            ref mut state,
+            ref sims_list_state,
+            ..
+        } = self.state
+        else {
+            return;
+        };
+
+        let ActiveState::EditingExternally { prev, .. } = mem::take(state) else {
+            return;
+        };
+
+        let res = match res {
+            Ok(()) => match self
+                .view
+                .selected(sims_list_state)
+                .and_then(|idx| self.simulations.get_mut(idx))
+            {
+                Some(sim) if let Some(discovery) = &self.discovery => {
+                    reload_sim(&self.client, &self.fill, discovery, sim).await
+                }
+                _ => Ok(()),
+            },
+            Err(err) => Err(err),
+        };
+
+        *state = match res {
+            Ok(()) => *prev,
+            Err(err) => ActiveState::ReloadingSimsFailed(human_err_report(&err)),
+        };
+    }
+
     fn render_state_picking_project(
         state: &mut ProjectPickerState,
         area: Rect,
@@ -604,6 +1356,7 @@ impl Batch {
     fn render_state_active(
         state: &ActiveState,
         simulations: &[SimulationParams],
+        view: &ListView,
         sims_list_state: &mut ListState,
         sim_detail_scrollbar_state: &mut ScrollbarState,
         area: Rect,
@@ -621,19 +1374,25 @@ impl Batch {
         Self::render_sims_list(
             state,
             simulations,
+            view,
             sims_list_state,
             left_area,
             buf,
         );
 
-        Self::render_sim_detail(
-            state,
-            simulations,
-            sims_list_state,
-            sim_detail_scrollbar_state,
-            right_area,
-            buf,
-        );
+        if let ActiveState::EditingParams { form, .. } = state {
+            Widget::render(&**form, right_area, buf);
+        } else {
+            Self::render_sim_detail(
+                state,
+                simulations,
+                view,
+                sims_list_state,
+                sim_detail_scrollbar_state,
+                right_area,
+                buf,
+            );
+        }
 
         match state {
             ActiveState::ReloadingSims => {
@@ -646,7 +1405,15 @@ impl Batch {
                 Self::render_exit_popup(area, buf);
             }
             ActiveState::ConfirmSubmit => {
-                Self::render_submit_confirmation_popup(simulations, area, buf);
+                Self::render_submit_confirmation_popup(
+                    simulations,
+                    view,
+                    area,
+                    buf,
+                );
+            }
+            ActiveState::ConfirmReset => {
+                Self::render_reset_confirmation_popup(simulations, area, buf);
             }
             ActiveState::Submitting {
                 bytes_count,
@@ -667,13 +1434,28 @@ impl Batch {
                     buf,
                 );
             }
-            ActiveState::ViewingList | ActiveState::ViewingDetail => {}
+            ActiveState::PickingParamsFile {
+                paths, list_state, ..
+            } => {
+                Self::render_params_file_picker(
+                    paths,
+                    &mut list_state.clone(),
+                    area,
+                    buf,
+                );
+            }
+            ActiveState::ViewingList
+            | ActiveState::ViewingDetail
+            | ActiveState::Searching
+            | ActiveState::EditingParams { .. }
+            | ActiveState::EditingExternally { .. } => {}
         }
     }
 
     fn render_sim_detail(
         state: &ActiveState,
         simulations: &[SimulationParams],
+        view: &ListView,
         sims_list_state: &ListState,
         sim_detail_scrollbar_state: &mut ScrollbarState,
         area: Rect,
@@ -685,8 +1467,8 @@ impl Batch {
                 state,
                 ActiveState::ViewingDetail | ActiveState::ViewingList
             ),
-            sim: sims_list_state
-                .selected()
+            sim: view
+                .selected(sims_list_state)
                 .and_then(|idx| simulations.get(idx)),
         };
 
@@ -696,25 +1478,59 @@ impl Batch {
     fn render_sims_list(
         state: &ActiveState,
         simulations: &[SimulationParams],
+        view: &ListView,
         sims_list_state: &mut ListState,
         area: Rect,
         buf: &mut Buffer,
     ) {
-        let block = Block::bordered()
-            .title(
-                line![format!(" Simulations ({}) ", simulations.len())]
-                    .centered(),
+        let title = if view.is_filtered() {
+            format!(
+                " Simulations ({}/{}) ",
+                view.visible().len(),
+                simulations.len()
             )
+        } else {
+            format!(" Simulations ({}) ", simulations.len())
+        };
+
+        let is_searching = matches!(state, ActiveState::Searching);
+
+        let mut status = vec![];
+
+        if is_searching || !view.query.is_empty() {
+            status.push(span!(
+                if is_searching { STYLE_ACCENT } else { STYLE_NORMAL };
+                format!(" /{}{} ", view.query, if is_searching { "▏" } else { "" })
+            ));
+        }
+
+        status.push(span!(format!(
+            " state: {} | sort: {} ",
+            view.state_filter
+                .map_or_else(|| "all".into(), |filter| filter.to_string()),
+            view.sort,
+        )));
+
+        let block = Block::bordered()
+            .title(line![title].centered())
+            .title_bottom(Line::from(status).centered())
             .border_set(border::PLAIN)
-            .border_style(if matches!(state, ActiveState::ViewingList) {
-                STYLE_NORMAL
-            } else {
-                STYLE_DIMMED
-            })
+            .border_style(
+                if matches!(
+                    state,
+                    ActiveState::ViewingList | ActiveState::Searching
+                ) {
+                    STYLE_NORMAL
+                } else {
+                    STYLE_DIMMED
+                },
+            )
             .style(
                 if matches!(
                     state,
-                    ActiveState::ViewingList | ActiveState::ViewingDetail
+                    ActiveState::ViewingList
+                        | ActiveState::ViewingDetail
+                        | ActiveState::Searching
                 ) {
                     STYLE_NORMAL
                 } else {
@@ -722,7 +1538,7 @@ impl Batch {
                 },
             );
 
-        let list = List::new(simulations.iter())
+        let list = List::new(view.visible().iter().map(|&idx| &simulations[idx]))
             .block(block)
             .highlight_spacing(HighlightSpacing::Always)
             .highlight_symbol(">> ")
@@ -764,6 +1580,45 @@ impl Batch {
         Widget::render(&paragraph, area, buf);
     }
 
+    fn render_params_file_picker(
+        paths: &[PathBuf],
+        list_state: &mut ListState,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let area = center(
+            area,
+            Constraint::Percentage(38),
+            Constraint::Length(u16::try_from(paths.len()).unwrap_or(5) + 2), // top and bottom border + content
+        );
+
+        let instructions = line![
+            " (",
+            span!(STYLE_ACCENT; "enter"),
+            ") open | (",
+            span!(STYLE_ACCENT; "esc"),
+            ") cancel ",
+        ];
+
+        let block = Block::bordered()
+            .title(line![span!(STYLE_BOLD; " Open in editor ")].centered())
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK);
+
+        let list = List::new(paths.iter().map(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        }))
+        .block(block)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_symbol(">> ")
+        .highlight_style(STYLE_ACCENT);
+
+        Widget::render(&Clear, area, buf);
+        StatefulWidget::render(list, area, buf, list_state);
+    }
+
     fn render_reloading_sims_popup(area: Rect, buf: &mut Buffer) {
         let area = center(
             area,
@@ -823,13 +1678,17 @@ impl Batch {
 
     fn render_submit_confirmation_popup(
         simulations: &[SimulationParams],
+        view: &ListView,
         area: Rect,
         buf: &mut Buffer,
     ) {
+        let hidden = view.hidden_submittable(simulations);
+
         let area = center(
             area,
             Constraint::Percentage(38),
-            Constraint::Length(6), // top and bottom border + content
+            // top and bottom border + content
+            Constraint::Length(if hidden > 0 { 7 } else { 6 }),
         );
 
         let instructions = line![
@@ -845,20 +1704,78 @@ impl Batch {
             .title_bottom(instructions.centered())
             .border_set(border::THICK);
 
-        let paragraph = Paragraph::new(text![
+        let mut text = text![
             "",
             line![
                 "A total of ",
                 span!(STYLE_ACCENT; format!(
                     "{} simulation(s)",
-                    simulations
-                        .iter()
-                        .filter(|sim_params| sim_params.is_submittable())
-                        .count(),
+                    view.submittable(simulations).count(),
                 )),
                 " will be submitted.",
             ]
             .centered(),
+        ];
+
+        if hidden > 0 {
+            text.push_line(
+                line![
+                    span!(STYLE_BOLD; format!("{hidden} selected")),
+                    " hidden by the filter will be left out.",
+                ]
+                .centered(),
+            );
+        }
+
+        text.push_line(line!["Are you sure you want to continue?"].centered());
+        text.push_line("");
+
+        let paragraph =
+            Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+
+        Widget::render(&Clear, area, buf);
+        Widget::render(&paragraph, area, buf);
+    }
+
+    fn render_reset_confirmation_popup(
+        simulations: &[SimulationParams],
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let area = center(
+            area,
+            Constraint::Percentage(38),
+            Constraint::Length(6), // top and bottom border + content
+        );
+
+        let instructions = line![
+            " (",
+            span!(STYLE_ERROR; "y"),
+            ") yes | (",
+            span!(STYLE_ERROR; "n"),
+            ") no ",
+        ];
+
+        let block = Block::bordered()
+            .title(
+                line![span!(STYLE_BOLD; " Resetting submission state ")]
+                    .centered(),
+            )
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .style(STYLE_ERROR);
+
+        let paragraph = Paragraph::new(text![
+            "",
+            line![
+                "A total of ",
+                span!(STYLE_BOLD; format!(
+                    "{} selected simulation(s)",
+                    simulations.iter().filter(|sim| is_resettable(sim)).count(),
+                )),
+                " will be marked as ready again.",
+            ]
+            .centered(),
             line!["Are you sure you want to continue?"].centered(),
             "",
         ])
@@ -895,8 +1812,30 @@ impl Batch {
             ") cycle list<->detail | (",
             span!(STYLE_ACCENT; "<space>"),
             ") toggle selection | (",
+            span!(STYLE_ACCENT; "e"),
+            ") edit params | (",
+            span!(STYLE_ACCENT; "E"),
+            ") open in editor | (",
+            span!(STYLE_ACCENT; "/"),
+            ") search | (",
+            span!(STYLE_ACCENT; "f"),
+            ") filter | (",
+            span!(STYLE_ACCENT; "s"),
+            ") sort | (",
+            span!(STYLE_ACCENT; "a"),
+            "/",
+            span!(STYLE_ACCENT; "n"),
+            "/",
+            span!(STYLE_ACCENT; "i"),
+            ") select all/none/invert | (",
+            span!(STYLE_ACCENT; "S"),
+            ") select same state | (",
+            span!(STYLE_ACCENT; "shift+up/down"),
+            ") select range | (",
             span!(STYLE_ACCENT; "ctrl+r"),
             ") reset submission state | (",
+            span!(STYLE_ACCENT; "R"),
+            ") reset selected | (",
             span!(STYLE_ACCENT; "r"),
             ") reload from disk | (",
             span!(STYLE_ACCENT; "ctrl+o"),
@@ -1024,6 +1963,7 @@ impl Widget for &mut Batch {
                 Batch::render_state_active(
                     state,
                     &self.simulations,
+                    &self.view,
                     sims_list_state,
                     sim_detail_scrollbar_state,
                     area,
@@ -1036,6 +1976,40 @@ impl Widget for &mut Batch {
     }
 }
 
+/// Writes edited params to disk and reloads the simulation from there, so that
+/// it reflects exactly what will be submitted.
+async fn save_params(
+    client: &Client,
+    fill: &ParamsFill,
+    discovery: &Discovery,
+    form: &ParamsForm,
+    sim: &mut SimulationParams,
+) -> eyre::Result<()> {
+    form.save().await?;
+
+    reload_sim(client, fill, discovery, sim).await
+}
+
+/// Reloads a single simulation from its dir, keeping its selection.
+async fn reload_sim(
+    client: &Client,
+    fill: &ParamsFill,
+    discovery: &Discovery,
+    sim: &mut SimulationParams,
+) -> eyre::Result<()> {
+    let mut reloaded =
+        SimulationParams::from_dir(client, fill, discovery, &sim.dir).await?;
+    reloaded.keep_remote(sim);
+
+    *sim = SimulationParams {
+        internal_id: sim.internal_id,
+        selected: sim.selected,
+        ..reloaded
+    };
+
+    Ok(())
+}
+
 impl From<&SimulationParams> for ListItem<'_> {
     fn from(p: &SimulationParams) -> Self {
         let style = if p.selected {
@@ -1054,9 +2028,26 @@ impl From<&SimulationParams> for ListItem<'_> {
             SubmissionState::Error(..) => {
                 spans.push(span!(STYLE_ERROR; "(error) "));
             }
-            SubmissionState::Sent { .. } => {
-                spans.push(span!(STYLE_SUCCESS; "(sent) "));
+            SubmissionState::Sent { .. } if p.changed_after_sent => {
+                spans.push(span!(STYLE_WARNING; "(changed after sent) "));
             }
+            SubmissionState::Sent { .. } => match p.remote.as_deref() {
+                Some(remote) => spans.push(span!(
+                    match remote.status {
+                        SimulationStatus::Success => STYLE_SUCCESS,
+                        SimulationStatus::Expired => STYLE_ERROR,
+                        SimulationStatus::Progress | SimulationStatus::Draft => {
+                            STYLE_WARNING
+                        }
+                    };
+                    format!("({}) ", human_simulation_status(remote.status))
+                )),
+                None => spans.push(span!(STYLE_SUCCESS; "(sent) ")),
+            },
+        }
+
+        if p.reload_error.is_some() {
+            spans.push(span!(STYLE_ERROR; "(invalid params) "));
         }
 
         if p.model_params.is_empty() {
@@ -1067,6 +2058,32 @@ impl From<&SimulationParams> for ListItem<'_> {
     }
 }
 
+/// Opens `path` in `$VISUAL` or `$EDITOR`, waiting for it to be closed.
+async fn open_in_editor(path: &Path) -> eyre::Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".into());
+
+    let words = shell_words::split(&editor)
+        .wrap_err_with(|| format!("parsing editor command `{editor}`"))?;
+    let Some((program, args)) = words.split_first() else {
+        eyre::bail!("editor command is empty");
+    };
+
+    let status = tokio::process::Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .await
+        .wrap_err_with(|| format!("running `{editor}`"))?;
+
+    if !status.success() {
+        eyre::bail!("`{editor}` exited with {status}");
+    }
+
+    Ok(())
+}
+
 fn center(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
     let [area] = Layout::horizontal([horizontal])
         .flex(Flex::Center)
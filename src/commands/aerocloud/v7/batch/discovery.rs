@@ -0,0 +1,167 @@
+use crate::{
+    commands::aerocloud::v7::batch::simulation_params::SimulationParams,
+    filter::glob_match, params_file::ParamsFormat,
+};
+use color_eyre::eyre::{self, WrapErr};
+use std::path::{Component, Path, PathBuf};
+use tokio::fs;
+
+/// Where simulations are looked for and which of them are picked up.
+///
+/// Simulations are named after their dir relative to the root one, e.g.
+/// `car/v3/yaw-sweep`, which is what globs are matched against. `*` matches any
+/// characters including `/`, `?` a single one. Globs without a `/` are also
+/// matched against the last dir alone.
+#[derive(Debug, Clone)]
+pub struct Discovery {
+    pub root_dir: PathBuf,
+    /// Look for simulations at any depth, in dirs containing `marker`, instead
+    /// of treating every dir in the root one as a simulation.
+    pub recursive: bool,
+    /// Params in any format when missing.
+    pub marker: Option<String>,
+    /// Simulations not matching any of these are skipped, unless empty.
+    pub include: Vec<String>,
+    /// Dirs matching any of these are skipped along with their content.
+    pub exclude: Vec<String>,
+}
+
+impl Discovery {
+    /// Read from the root dir, one exclude glob per line with `#` starting
+    /// comments.
+    pub const IGNORE_FILENAME: &str = ".nfignore";
+
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self {
+            root_dir,
+            recursive: false,
+            marker: None,
+            include: vec![],
+            exclude: vec![],
+        }
+    }
+
+    /// Dirs of all simulations, sorted.
+    pub async fn sim_dirs(&self) -> eyre::Result<Vec<PathBuf>> {
+        if !fs::metadata(&self.root_dir).await?.is_dir() {
+            eyre::bail!("`{}` is not a directory", self.root_dir.display());
+        }
+
+        let ignored = self.read_ignore_file().await?;
+
+        let mut sim_dirs = vec![];
+        let mut pending = vec![self.root_dir.clone()];
+
+        while let Some(dir) = pending.pop() {
+            let mut dir_stream = fs::read_dir(&dir)
+                .await
+                .wrap_err_with(|| format!("listing dir `{}`", dir.display()))?;
+
+            while let Some(entry) = dir_stream
+                .next_entry()
+                .await
+                .wrap_err("iterating dir stream")?
+            {
+                let path = entry.path();
+
+                // NOTE: hidden dirs hold tooling state (`.git` and the like)
+                // rather than simulations.
+                if !path.is_dir()
+                    || entry.file_name().to_string_lossy().starts_with('.')
+                {
+                    continue;
+                }
+
+                let name = self.relative_name(&path)?;
+
+                if self
+                    .exclude
+                    .iter()
+                    .chain(&ignored)
+                    .any(|p| matches(p, &name))
+                {
+                    continue;
+                }
+
+                if self.recursive && !self.is_marked(&path)? {
+                    pending.push(path);
+                    continue;
+                }
+
+                if self.include.is_empty()
+                    || self.include.iter().any(|p| matches(p, &name))
+                {
+                    sim_dirs.push(path);
+                }
+            }
+        }
+
+        sim_dirs.sort_unstable();
+
+        Ok(sim_dirs)
+    }
+
+    /// `path` relative to the root dir, `/` separated, which is what
+    /// simulations are named after.
+    pub fn relative_name(&self, path: &Path) -> eyre::Result<String> {
+        let relative = path.strip_prefix(&self.root_dir).unwrap_or(path);
+
+        let components = relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name),
+                _ => None,
+            })
+            .map(|name| {
+                name.to_str().ok_or_else(|| {
+                    eyre::eyre!(
+                        "dir name {:?} contains invalid utf-8 characters",
+                        name
+                    )
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        if components.is_empty() {
+            eyre::bail!("no name for `{}`", path.display());
+        }
+
+        Ok(components.join("/"))
+    }
+
+    fn is_marked(&self, dir: &Path) -> eyre::Result<bool> {
+        Ok(match &self.marker {
+            Some(marker) => dir.join(marker).is_file(),
+            None => ParamsFormat::find(&dir.join(SimulationParams::PARAMS_STEM))?
+                .is_some(),
+        })
+    }
+
+    async fn read_ignore_file(&self) -> eyre::Result<Vec<String>> {
+        let path = self.root_dir.join(Self::IGNORE_FILENAME);
+
+        if !path.is_file() {
+            return Ok(vec![]);
+        }
+
+        let buf = fs::read_to_string(&path)
+            .await
+            .wrap_err_with(|| format!("reading `{}`", path.display()))?;
+
+        Ok(buf
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.trim_matches('/').to_owned())
+            .collect())
+    }
+}
+
+fn matches(pattern: &str, name: &str) -> bool {
+    glob_match(pattern, name)
+        || (!pattern.contains('/')
+            && name
+                .rsplit('/')
+                .next()
+                .is_some_and(|leaf| glob_match(pattern, leaf)))
+}
@@ -0,0 +1,193 @@
+use crate::commands::aerocloud::v7::batch::simulation_params::{
+    SimulationParams, SubmissionState,
+};
+use ratatui::widgets::ListState;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateFilter {
+    Ready,
+    Sent,
+    Error,
+}
+
+impl StateFilter {
+    pub fn of(state: &SubmissionState) -> Self {
+        match state {
+            SubmissionState::Ready | SubmissionState::Sending => Self::Ready,
+            SubmissionState::Sent { .. } => Self::Sent,
+            SubmissionState::Error(..) => Self::Error,
+        }
+    }
+
+    pub fn matches(self, state: &SubmissionState) -> bool {
+        matches!(
+            (self, state),
+            (
+                Self::Ready,
+                SubmissionState::Ready | SubmissionState::Sending
+            ) | (Self::Sent, SubmissionState::Sent { .. })
+                | (Self::Error, SubmissionState::Error(..))
+        )
+    }
+
+    /// Cycles through all filters, `None` meaning no filter.
+    pub fn next(filter: Option<Self>) -> Option<Self> {
+        match filter {
+            None => Some(Self::Ready),
+            Some(Self::Ready) => Some(Self::Sent),
+            Some(Self::Sent) => Some(Self::Error),
+            Some(Self::Error) => None,
+        }
+    }
+}
+
+impl fmt::Display for StateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ready => "ready",
+            Self::Sent => "sent",
+            Self::Error => "error",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortBy {
+    #[default]
+    Name,
+    Size,
+    State,
+}
+
+impl SortBy {
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            Self::Name => Self::Size,
+            Self::Size => Self::State,
+            Self::State => Self::Name,
+        }
+    }
+}
+
+impl fmt::Display for SortBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Name => "name",
+            Self::Size => "size",
+            Self::State => "state",
+        })
+    }
+}
+
+/// Bulk change to which of the shown simulations are selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    None,
+    Invert,
+    /// Exactly the ones in the given state.
+    Only(StateFilter),
+}
+
+/// Which simulations are shown in the list and in which order, as indices into
+/// all of them.
+#[derive(Debug, Clone, Default)]
+pub struct ListView {
+    pub query: String,
+    pub state_filter: Option<StateFilter>,
+    pub sort: SortBy,
+    visible: Vec<usize>,
+}
+
+impl ListView {
+    pub fn refresh(&mut self, sims: &[SimulationParams]) {
+        let query = self.query.to_lowercase();
+
+        self.visible = (0..sims.len())
+            .filter(|&idx| {
+                let sim = &sims[idx];
+
+                self.state_filter
+                    .is_none_or(|filter| filter.matches(&sim.submission_state))
+                    && (query.is_empty()
+                        || sim.params.name.to_lowercase().contains(&query)
+                        || sim
+                            .params
+                            .revision
+                            .as_ref()
+                            .is_some_and(|r| r.to_lowercase().contains(&query)))
+            })
+            .collect();
+
+        self.visible.sort_by(|&a, &b| {
+            let (a, b) = (&sims[a], &sims[b]);
+
+            match self.sort {
+                SortBy::Name => std::cmp::Ordering::Equal,
+                SortBy::Size => b.files_size().cmp(&a.files_size()),
+                SortBy::State => state_rank(&a.submission_state)
+                    .cmp(&state_rank(&b.submission_state)),
+            }
+            .then_with(|| a.params.name.cmp(&b.params.name))
+        });
+    }
+
+    pub fn visible(&self) -> &[usize] {
+        &self.visible
+    }
+
+    /// Index of the simulation under the cursor.
+    pub fn selected(&self, list_state: &ListState) -> Option<usize> {
+        list_state
+            .selected()
+            .and_then(|idx| self.visible.get(idx).copied())
+    }
+
+    /// Applies `selection` to the shown simulations, hidden ones are left
+    /// untouched.
+    pub fn select(&self, sims: &mut [SimulationParams], selection: Selection) {
+        for &idx in &self.visible {
+            let sim = &mut sims[idx];
+
+            sim.selected = match selection {
+                Selection::All => true,
+                Selection::None => false,
+                Selection::Invert => !sim.selected,
+                Selection::Only(filter) => filter.matches(&sim.submission_state),
+            };
+        }
+    }
+
+    /// Simulations to submit: the submittable ones among those shown, so that
+    /// hidden ones are never sent along.
+    pub fn submittable<'a>(
+        &'a self,
+        sims: &'a [SimulationParams],
+    ) -> impl Iterator<Item = &'a SimulationParams> {
+        self.visible
+            .iter()
+            .map(|&idx| &sims[idx])
+            .filter(|sim| sim.is_submittable())
+    }
+
+    /// Submittable simulations left out of submissions as they're hidden.
+    pub fn hidden_submittable(&self, sims: &[SimulationParams]) -> usize {
+        sims.iter().filter(|sim| sim.is_submittable()).count()
+            - self.submittable(sims).count()
+    }
+
+    pub fn is_filtered(&self) -> bool {
+        !self.query.is_empty() || self.state_filter.is_some()
+    }
+}
+
+fn state_rank(state: &SubmissionState) -> u8 {
+    match state {
+        SubmissionState::Error(..) => 0,
+        SubmissionState::Ready => 1,
+        SubmissionState::Sending => 2,
+        SubmissionState::Sent { .. } => 3,
+    }
+}
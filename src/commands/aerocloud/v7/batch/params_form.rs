@@ -0,0 +1,810 @@
+use crate::{
+    aerocloud::{
+        extra_types::{CreateSimulationV7ParamsFromJson, FileV7ParamsFromJson},
+        schema::{self, Definition},
+    },
+    commands::aerocloud::v7::batch::{
+        STYLE_ACCENT, STYLE_BOLD, STYLE_DIMMED, STYLE_ERROR, STYLE_NORMAL,
+        STYLE_WARNING,
+        simulation_params::{ModelParams, SimulationParams},
+    },
+    params_file::ParamsFormat,
+};
+use color_eyre::eyre::{self, WrapErr};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use itertools::Itertools;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    macros::{line, span},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+use serde_json::{Map, Value, json};
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+use tokio::fs;
+use uuid::Uuid;
+
+const LABEL_WIDTH: usize = 26;
+
+/// Form to edit the params of a simulation and of its model files, which are
+/// written back to their JSON files on save. YAML and TOML ones can only be
+/// edited in an external editor.
+#[derive(Debug, Clone)]
+pub struct ParamsForm {
+    sim_name: String,
+    sim_path: PathBuf,
+    file_paths: Vec<PathBuf>,
+    fields: Vec<Field>,
+    focused: usize,
+    error: Option<String>,
+}
+
+/// What should happen after a key has been handled by the form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormAction {
+    Continue,
+    Cancel,
+    Save,
+}
+
+/// The JSON file a field is stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Doc {
+    Sim,
+    File(usize),
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Text,
+    Id,
+    Number {
+        optional: bool,
+        range: Option<RangeInclusive<f64>>,
+    },
+    Numbers {
+        optional: bool,
+        len: Option<usize>,
+    },
+    /// `none` is the option which unsets the field.
+    Choice {
+        options: &'static [&'static str],
+        none: Option<&'static str>,
+    },
+    Toggle,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    doc: Doc,
+    keys: Vec<String>,
+    section: Option<String>,
+    label: &'static str,
+    kind: Kind,
+    input: String,
+    initial: String,
+    error: Option<String>,
+}
+
+impl Field {
+    fn new(
+        doc: Doc,
+        keys: &[&str],
+        label: &'static str,
+        kind: Kind,
+        input: String,
+    ) -> Self {
+        Self {
+            doc,
+            keys: keys.iter().map(ToString::to_string).collect(),
+            section: None,
+            label,
+            kind,
+            initial: input.clone(),
+            input,
+            error: None,
+        }
+    }
+
+    fn with_section(mut self, section: impl Into<String>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+
+    fn is_changed(&self) -> bool {
+        self.input != self.initial
+    }
+
+    /// Parses the input, `None` meaning the key is unset.
+    fn value(&self) -> Result<Option<Value>, String> {
+        let input = self.input.trim();
+
+        match &self.kind {
+            Kind::Text => {
+                Ok((!input.is_empty()).then(|| Value::String(input.into())))
+            }
+            Kind::Id => {
+                if input.is_empty() {
+                    return Ok(None);
+                }
+
+                Uuid::parse_str(input)
+                    .map(|id| Some(Value::String(id.to_string())))
+                    .map_err(|_| "not a valid id".into())
+            }
+            Kind::Number { optional, range } => {
+                if input.is_empty() {
+                    return if *optional {
+                        Ok(None)
+                    } else {
+                        Err("required".into())
+                    };
+                }
+
+                let n = parse_number(input)?;
+
+                if let Some(range) = range
+                    && !range.contains(&n)
+                {
+                    return Err(format!(
+                        "must be between {} and {}",
+                        range.start(),
+                        range.end()
+                    ));
+                }
+
+                Ok(Some(json!(n)))
+            }
+            Kind::Numbers { optional, len } => {
+                if input.is_empty() {
+                    return if *optional {
+                        Ok(None)
+                    } else {
+                        Err("at least one value is required".into())
+                    };
+                }
+
+                let ns = input
+                    .split(',')
+                    .map(|s| parse_number(s.trim()))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if let Some(len) = len
+                    && ns.len() != *len
+                {
+                    return Err(format!("exactly {len} values are required"));
+                }
+
+                Ok(Some(json!(ns)))
+            }
+            Kind::Choice { none, .. } => {
+                Ok((Some(input) != *none).then(|| Value::String(input.into())))
+            }
+            Kind::Toggle => Ok(Some(Value::Bool(input == "yes"))),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match (&self.kind, key.code) {
+            (Kind::Choice { options, .. }, KeyCode::Left) => {
+                self.input = cycle(options, &self.input, -1);
+            }
+            (
+                Kind::Choice { options, .. },
+                KeyCode::Right | KeyCode::Char(' '),
+            ) => {
+                self.input = cycle(options, &self.input, 1);
+            }
+            (
+                Kind::Toggle,
+                KeyCode::Left | KeyCode::Right | KeyCode::Char(' '),
+            ) => {
+                self.input = cycle(&["no", "yes"], &self.input, 1);
+            }
+            (Kind::Choice { .. } | Kind::Toggle, _) => {}
+            (_, KeyCode::Backspace) => {
+                self.input.pop();
+            }
+            (_, KeyCode::Char(c)) => {
+                self.input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn line(&self, has_focus: bool) -> Line<'_> {
+        let style = if has_focus {
+            STYLE_ACCENT
+        } else {
+            STYLE_NORMAL
+        };
+
+        let input = match self.kind {
+            Kind::Choice { .. } | Kind::Toggle if has_focus => {
+                format!("< {} >", self.input)
+            }
+            Kind::Choice { .. } | Kind::Toggle => self.input.clone(),
+            _ if has_focus => format!("{}▏", self.input),
+            _ => self.input.clone(),
+        };
+
+        let mut spans = vec![
+            Span::raw(if has_focus { ">> " } else { "   " }),
+            Span::styled(
+                format!("{:LABEL_WIDTH$}", format!("{}:", self.label)),
+                STYLE_BOLD,
+            ),
+            Span::styled(input, style),
+        ];
+
+        if self.is_changed() {
+            spans.push(span!(STYLE_WARNING; " *"));
+        }
+
+        if let Some(err) = &self.error {
+            spans.push(span!(STYLE_ERROR; format!("  {err}")));
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl ParamsForm {
+    pub fn new(sim: &SimulationParams) -> Self {
+        let mut fields = Self::sim_fields(&sim.params);
+        let mut file_paths = vec![];
+
+        if let ModelParams::New { files } = &sim.model_params {
+            for (idx, file) in files.iter().enumerate() {
+                fields.extend(Self::file_fields(
+                    Doc::File(idx),
+                    &file.filename,
+                    &file.params,
+                ));
+                file_paths.push(file.params_path.clone());
+            }
+        }
+
+        let mut form = Self {
+            sim_name: sim.params.name.clone(),
+            sim_path: sim.params_path.clone(),
+            file_paths,
+            fields,
+            focused: 0,
+            error: None,
+        };
+
+        form.validate();
+        form
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn sim_fields(params: &CreateSimulationV7ParamsFromJson) -> Vec<Field> {
+        let doc = Doc::Sim;
+
+        vec![
+            Field::new(
+                doc,
+                &["revision"],
+                "Revision",
+                Kind::Text,
+                params.revision.clone().unwrap_or_default(),
+            )
+            .with_section("Simulation"),
+            Field::new(
+                doc,
+                &["model_id"],
+                "Reusable model id",
+                Kind::Id,
+                params
+                    .model_id
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            ),
+            Field::new(
+                doc,
+                &["quality"],
+                "Quality",
+                Kind::Choice {
+                    options: &["basic", "standard", "pro"],
+                    none: None,
+                },
+                params.quality.to_string(),
+            ),
+            Field::new(
+                doc,
+                &["yaw_angles"],
+                "Yaw angles (°)",
+                Kind::Numbers {
+                    optional: false,
+                    len: None,
+                },
+                params.yaw_angles.iter().map(|y| y.0).join(", "),
+            ),
+            Field::new(
+                doc,
+                &["fluid"],
+                "Fluid",
+                Kind::Choice {
+                    options: &["air", "water"],
+                    none: None,
+                },
+                params.fluid.to_string(),
+            ),
+            Field::new(
+                doc,
+                &["fluid_speed"],
+                "Speed (m/s)",
+                Kind::Number {
+                    optional: false,
+                    range: Some(0.001..=100.0),
+                },
+                params.fluid_speed.0.to_string(),
+            ),
+            Field::new(
+                doc,
+                &["has_ground"],
+                "Ground",
+                Kind::Toggle,
+                bool_to_input(params.has_ground),
+            ),
+            Field::new(
+                doc,
+                &["ground_offset"],
+                "Ground offset (m)",
+                Kind::Number {
+                    optional: false,
+                    range: None,
+                },
+                params.ground_offset.0.to_string(),
+            ),
+            Field::new(
+                doc,
+                &["is_ground_moving"],
+                "Moving ground",
+                Kind::Toggle,
+                bool_to_input(params.is_ground_moving),
+            ),
+            Field::new(
+                doc,
+                &["ceiling", "condition"],
+                "Ceiling",
+                Kind::Choice {
+                    options: &["none", "slip", "moving"],
+                    none: Some("none"),
+                },
+                params
+                    .ceiling
+                    .as_ref()
+                    .map_or_else(|| "none".into(), |c| c.condition.to_string()),
+            ),
+            Field::new(
+                doc,
+                &["ceiling", "offset"],
+                "Ceiling offset (m)",
+                Kind::Number {
+                    optional: true,
+                    range: None,
+                },
+                params
+                    .ceiling
+                    .as_ref()
+                    .map(|c| c.offset.0.to_string())
+                    .unwrap_or_default(),
+            ),
+            Field::new(
+                doc,
+                &["boundary_layer_treatment"],
+                "Boundary layer treatment",
+                Kind::Choice {
+                    options: &[
+                        "default",
+                        "wall_functions",
+                        "resolved_boundary_layer",
+                    ],
+                    none: Some("default"),
+                },
+                params
+                    .boundary_layer_treatment
+                    .map_or_else(|| "default".into(), |v| v.to_string()),
+            ),
+        ]
+    }
+
+    fn file_fields(
+        doc: Doc,
+        filename: &str,
+        params: &FileV7ParamsFromJson,
+    ) -> Vec<Field> {
+        let mut fields = vec![
+            Field::new(
+                doc,
+                &["unit"],
+                "Unit",
+                Kind::Choice {
+                    options: &["mm", "cm", "m", "inches"],
+                    none: None,
+                },
+                params.unit.to_string(),
+            )
+            .with_section(format!("File `{filename}`")),
+            Field::new(
+                doc,
+                &["rotation"],
+                "Rotation (quaternion)",
+                Kind::Numbers {
+                    optional: true,
+                    len: Some(4),
+                },
+                params
+                    .rotation
+                    .as_ref()
+                    .map(|q| q.0.iter().join(", "))
+                    .unwrap_or_default(),
+            ),
+        ];
+
+        for (name, part) in &params.parts {
+            let keys = |key| ["parts", name.as_str(), key];
+
+            fields.extend([
+                Field::new(
+                    doc,
+                    &keys("rolling"),
+                    "Rolling",
+                    Kind::Toggle,
+                    bool_to_input(part.rolling.unwrap_or(false)),
+                )
+                .with_section(format!("Part `{name}` of `{filename}`")),
+                Field::new(
+                    doc,
+                    &keys("refined"),
+                    "Refined",
+                    Kind::Toggle,
+                    bool_to_input(part.refined.unwrap_or(false)),
+                ),
+                Field::new(
+                    doc,
+                    &keys("is_porous"),
+                    "Porous",
+                    Kind::Toggle,
+                    bool_to_input(part.is_porous.unwrap_or(false)),
+                ),
+                Field::new(
+                    doc,
+                    &keys("darcy_coeff"),
+                    "Darcy coeff",
+                    Kind::Number {
+                        optional: true,
+                        range: None,
+                    },
+                    part.darcy_coeff
+                        .as_ref()
+                        .map(|v| v.0.to_string())
+                        .unwrap_or_default(),
+                ),
+                Field::new(
+                    doc,
+                    &keys("forchheimer_coeff"),
+                    "Forchheimer coeff",
+                    Kind::Number {
+                        optional: true,
+                        range: None,
+                    },
+                    part.forchheimer_coeff
+                        .as_ref()
+                        .map(|v| v.0.to_string())
+                        .unwrap_or_default(),
+                ),
+            ]);
+        }
+
+        fields
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none() && self.fields.iter().all(|f| f.error.is_none())
+    }
+
+    pub fn set_error(&mut self, err: String) {
+        self.error = Some(err);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> FormAction {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => return FormAction::Cancel,
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                return if self.is_valid() {
+                    FormAction::Save
+                } else {
+                    FormAction::Continue
+                };
+            }
+            (KeyCode::Up | KeyCode::BackTab, _) => {
+                self.focused = self.focused.saturating_sub(1);
+            }
+            (KeyCode::Down | KeyCode::Tab, _) => {
+                self.focused = (self.focused + 1).min(self.fields.len() - 1);
+            }
+            (_, KeyModifiers::CONTROL) => {}
+            _ => {
+                if let Some(field) = self.fields.get_mut(self.focused) {
+                    field.handle_key(key);
+                }
+
+                self.validate();
+            }
+        }
+
+        FormAction::Continue
+    }
+
+    /// Validates each field on its own, then params as a whole the same way
+    /// they'd be validated when loaded from disk.
+    fn validate(&mut self) {
+        for field in &mut self.fields {
+            field.error = field.value().err();
+        }
+
+        self.error = self.validate_docs().err().map(|err| format!("{err:#}"));
+    }
+
+    fn validate_docs(&self) -> eyre::Result<()> {
+        let sim_doc = self.doc_value(Doc::Sim)?;
+        ensure_is_valid(&sim_doc, Definition::Simulation)?;
+        serde_json::from_value::<CreateSimulationV7ParamsFromJson>(sim_doc)
+            .wrap_err("invalid simulation params")?;
+
+        for idx in 0..self.file_paths.len() {
+            let file_doc = self.doc_value(Doc::File(idx))?;
+            ensure_is_valid(&file_doc, Definition::File)?;
+            serde_json::from_value::<FileV7ParamsFromJson>(file_doc)
+                .wrap_err("invalid file params")?
+                .ensure_is_valid()?;
+        }
+
+        Ok(())
+    }
+
+    /// Builds a document out of all the fields stored in it.
+    fn doc_value(&self, doc: Doc) -> eyre::Result<Value> {
+        let mut map = Map::new();
+
+        for field in self.fields.iter().filter(|f| f.doc == doc) {
+            let value = field.value().map_err(|err| eyre::eyre!(err))?;
+            set_value(&mut map, &field.keys, value);
+        }
+
+        prune_ceiling(&mut map);
+
+        Ok(Value::Object(map))
+    }
+
+    /// Paths of all the files the form is stored in, starting with the
+    /// simulation params.
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.sim_path).chain(&self.file_paths)
+    }
+
+    /// Creates `path` empty if it doesn't exist yet, so that it can be edited
+    /// elsewhere.
+    ///
+    /// NOTE: none of the current values come from a missing file, they're all
+    /// defaults which would be pinned to the simulation if written to it.
+    pub async fn ensure_exists(&self, path: &Path) -> eyre::Result<()> {
+        if path.exists() {
+            return Ok(());
+        }
+
+        if !self.paths().any(|p| p == path) {
+            eyre::bail!("`{}` is not part of the simulation", path.display());
+        }
+
+        fs::write(path, "{}\n")
+            .await
+            .wrap_err_with(|| format!("writing `{}`", path.display()))
+    }
+
+    /// Writes changed fields to their files, leaving everything else as is.
+    pub async fn save(&self) -> eyre::Result<()> {
+        let docs = std::iter::once(Doc::Sim)
+            .chain((0..self.file_paths.len()).map(Doc::File));
+
+        for doc in docs {
+            let changed: Vec<&Field> = self
+                .fields
+                .iter()
+                .filter(|f| f.doc == doc && f.is_changed())
+                .collect();
+
+            if changed.is_empty() {
+                continue;
+            }
+
+            let path = match doc {
+                Doc::Sim => &self.sim_path,
+                Doc::File(idx) => &self.file_paths[idx],
+            };
+
+            // NOTE: rewriting YAML or TOML would drop comments and formatting.
+            if let Some(format) = ParamsFormat::from_path(path)
+                && format != ParamsFormat::Json
+            {
+                eyre::bail!(
+                    "`{}` is {format}, edit it in the external editor instead",
+                    path.display()
+                );
+            }
+
+            let mut map = if path.exists() {
+                let buf = fs::read(path)
+                    .await
+                    .wrap_err_with(|| format!("reading `{}`", path.display()))?;
+
+                serde_json::from_slice(&buf)
+                    .wrap_err_with(|| format!("parsing `{}`", path.display()))?
+            } else {
+                Map::new()
+            };
+
+            for field in changed {
+                let value = field.value().map_err(|err| eyre::eyre!(err))?;
+                set_value(&mut map, &field.keys, value);
+            }
+
+            if doc == Doc::Sim {
+                prune_ceiling(&mut map);
+            }
+
+            let mut buf = serde_json::to_vec_pretty(&map)?;
+            buf.push(b'\n');
+
+            fs::write(path, &buf)
+                .await
+                .wrap_err_with(|| format!("writing `{}`", path.display()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Widget for &ParamsForm {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let instructions = line![
+            " (",
+            span!(STYLE_ACCENT; "↑/↓"),
+            ") move | (",
+            span!(STYLE_ACCENT; "←/→"),
+            ") change | (",
+            span!(if self.is_valid() { STYLE_ACCENT } else { STYLE_DIMMED }; "ctrl+s"),
+            ") save | (",
+            span!(STYLE_ACCENT; "esc"),
+            ") discard ",
+        ];
+
+        let block = Block::bordered()
+            .title(
+                line![
+                    span!(STYLE_BOLD; format!(" Editing `{}` ", self.sim_name))
+                ]
+                .centered(),
+            )
+            .title_bottom(instructions.centered())
+            .border_set(border::PLAIN);
+
+        let mut lines = vec![];
+        let mut focused_line = 0;
+
+        if let Some(err) = &self.error {
+            for line in err.lines() {
+                lines.push(Line::styled(line.to_owned(), STYLE_ERROR));
+            }
+            lines.push(Line::default());
+        }
+
+        for (idx, field) in self.fields.iter().enumerate() {
+            if let Some(section) = &field.section {
+                if idx > 0 {
+                    lines.push(Line::default());
+                }
+                lines.push(Line::styled(section.clone(), STYLE_BOLD));
+            }
+
+            if idx == self.focused {
+                focused_line = lines.len();
+            }
+
+            lines.push(field.line(idx == self.focused));
+        }
+
+        // NOTE: keep the focused field around the middle.
+        let height = usize::from(block.inner(area).height);
+        let offset = focused_line.saturating_sub(height / 2);
+
+        Paragraph::new(lines)
+            .scroll((u16::try_from(offset).unwrap_or(0), 0))
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+/// Sets or removes the value at `keys`, creating or removing objects on the
+/// way as needed.
+fn set_value(
+    map: &mut Map<String, Value>,
+    keys: &[String],
+    value: Option<Value>,
+) {
+    let [key, rest @ ..] = keys else {
+        return;
+    };
+
+    if rest.is_empty() {
+        match value {
+            Some(value) => map.insert(key.clone(), value),
+            None => map.remove(key),
+        };
+        return;
+    }
+
+    let entry = map
+        .entry(key.clone())
+        .or_insert_with(|| Value::Object(Map::new()));
+
+    if !entry.is_object() {
+        *entry = Value::Object(Map::new());
+    }
+
+    if let Value::Object(inner) = entry {
+        set_value(inner, rest, value);
+
+        if inner.is_empty() {
+            map.remove(key);
+        }
+    }
+}
+
+/// Checks `doc` against the schema, the same way params files are.
+fn ensure_is_valid(doc: &Value, definition: Definition) -> eyre::Result<()> {
+    let violations = schema::validate(doc, definition);
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    eyre::bail!("{}", violations.iter().join("; "));
+}
+
+/// A ceiling without a condition is no ceiling at all.
+fn prune_ceiling(map: &mut Map<String, Value>) {
+    if map
+        .get("ceiling")
+        .is_some_and(|ceiling| ceiling.get("condition").is_none())
+    {
+        map.remove("ceiling");
+    }
+}
+
+fn parse_number(s: &str) -> Result<f64, String> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .ok_or_else(|| format!("`{s}` is not a number"))
+}
+
+fn cycle(options: &[&str], current: &str, step: isize) -> String {
+    let idx = options.iter().position(|o| *o == current).unwrap_or(0);
+    let next = (idx.cast_signed() + step).rem_euclid(options.len().cast_signed());
+
+    options[next.cast_unsigned()].to_owned()
+}
+
+fn bool_to_input(b: bool) -> String {
+    if b { "yes" } else { "no" }.into()
+}
@@ -1,7 +1,9 @@
 use crate::{
     aerocloud::{
-        Client, fmt_progenitor_err,
-        types::{ListPageProjectsV7, PaginationOffset, ProjectStatus, ProjectV7},
+        Client,
+        cache::Cache,
+        fmt_progenitor_err,
+        types::{ListPageProjectsV7, ProjectStatus, ProjectV7},
     },
     commands::aerocloud::v7::batch::{
         Event, STYLE_ACCENT, STYLE_BOLD, STYLE_ERROR,
@@ -35,36 +37,38 @@ pub enum ProjectPickerState {
     },
 }
 
-pub fn refresh_projects_in_background(client: Client, tx: mpsc::Sender<Event>) {
+pub fn refresh_projects_in_background(
+    client: Client,
+    cache: Cache,
+    tx: mpsc::Sender<Event>,
+) {
     tokio::spawn(async move {
         tx.send(Event::ProjectsLoading).await?;
 
-        let res = fetch_projects(client).await;
+        let res = fetch_projects(&client, &cache).await;
         tx.send(Event::ProjectsUpdated(res)).await?;
 
         Ok::<(), eyre::Report>(())
     });
 }
 
-async fn fetch_projects(client: Client) -> eyre::Result<Vec<ProjectV7>> {
-    let mut projects = vec![];
-    let mut offset = PaginationOffset(0u64);
+async fn fetch_projects(
+    client: &Client,
+    cache: &Cache,
+) -> eyre::Result<Vec<ProjectV7>> {
+    let mut projects = cache
+        .projects(|offset| async move {
+            let ListPageProjectsV7 { items, nav } = client
+                .projects_v7_list(None, Some(&offset), None)
+                .await
+                .map_err(fmt_progenitor_err)?
+                .into_inner();
 
-    loop {
-        let ListPageProjectsV7 { items, nav } = client
-            .projects_v7_list(None, Some(&offset), Some(ProjectStatus::Active))
-            .await
-            .map_err(fmt_progenitor_err)?
-            .into_inner();
+            Ok((items, nav))
+        })
+        .await?;
 
-        projects.extend(items);
-
-        if let Some(next_offset) = nav.next_offset {
-            offset = PaginationOffset(next_offset);
-        } else {
-            break;
-        }
-    }
+    projects.retain(|project| project.status == ProjectStatus::Active);
 
     Ok(projects)
 }
@@ -74,6 +78,7 @@ impl ProjectPickerState {
         &mut self,
         event: Event,
         client: Client,
+        cache: Cache,
         tx: mpsc::Sender<Event>,
     ) -> eyre::Result<()> {
         if let Event::KeyPressed(key_event) = &event
@@ -114,7 +119,7 @@ impl ProjectPickerState {
                 Event::KeyPressed(key_event)
                     if key_event.code == KeyCode::Char('r') =>
                 {
-                    refresh_projects_in_background(client, tx);
+                    refresh_projects_in_background(client, cache, tx);
                 }
                 _ => {}
             },
@@ -133,7 +138,7 @@ impl ProjectPickerState {
                 Event::KeyPressed(key_event)
                     if key_event.code == KeyCode::Char('r') =>
                 {
-                    refresh_projects_in_background(client, tx);
+                    refresh_projects_in_background(client, cache, tx);
                 }
                 Event::KeyPressed(key_event)
                     if key_event.code == KeyCode::Enter =>
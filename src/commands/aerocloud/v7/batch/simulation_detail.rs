@@ -3,6 +3,7 @@ use crate::{
         fmt,
         types::{
             Ceiling, CeilingCondition, ModelV7, ModelV7FilesItem, Quaternion,
+            SimulationStatus, SimulationV7,
         },
     },
     commands::aerocloud::v7::batch::{
@@ -59,6 +60,27 @@ impl<'a> SimulationDetail<'a> {
         format!(" {} ", sim.params.name).into()
     }
 
+    fn reload_error(sim: &'a SimulationParams, lines: &mut Vec<Line<'a>>) {
+        let Some(err) = &sim.reload_error else {
+            return;
+        };
+
+        lines.push(
+            Line::raw("Failed to reload, not submittable until fixed:")
+                .style(STYLE_ERROR),
+        );
+        lines.push(Line::default());
+
+        for line in err.lines() {
+            lines.push(
+                Line::from(vec![Span::from("  "), Span::from(line)])
+                    .style(STYLE_ERROR),
+            );
+        }
+
+        lines.push(Line::default());
+    }
+
     fn submission_state(sim: &'a SimulationParams, lines: &mut Vec<Line<'a>>) {
         match sim.submission_state {
             SubmissionState::Ready | SubmissionState::Sending => {
@@ -82,12 +104,77 @@ impl<'a> SimulationDetail<'a> {
                     Line::from(format!("Open in browser: {browser_url}"))
                         .style(STYLE_SUCCESS),
                 );
+
+                if sim.changed_after_sent {
+                    lines.push(Line::default());
+                    lines.push(Line::styled(
+                        "Files changed after being sent, reset the submission state to submit again.",
+                        STYLE_WARNING,
+                    ));
+                }
+
+                if let Some(remote) = &sim.remote {
+                    lines.push(Line::default());
+
+                    Self::remote_lines(remote, lines);
+                }
             }
         }
 
         lines.push(Line::default());
     }
 
+    fn remote_lines(remote: &SimulationV7, lines: &mut Vec<Line<'a>>) {
+        lines.push(Line::from(vec![
+            Span::styled("Status: ", STYLE_BOLD),
+            Span::styled(
+                fmt::human_simulation_status(remote.status),
+                match remote.status {
+                    SimulationStatus::Success => STYLE_SUCCESS,
+                    SimulationStatus::Expired => STYLE_ERROR,
+                    SimulationStatus::Progress | SimulationStatus::Draft => {
+                        STYLE_WARNING
+                    }
+                },
+            ),
+        ]));
+
+        let Some(results) = &remote.results else {
+            return;
+        };
+
+        lines.push(Line::default());
+
+        lines.push(Line::styled(
+            format!(
+                "{:>8} {:>8} {:>8} {:>8} {:>10} {:>10}",
+                "Yaw", "Cd", "Cl", "Cs", "Cda (m²)", "Fd (N)"
+            ),
+            STYLE_BOLD,
+        ));
+
+        for res in &results.yaw_angles {
+            lines.push(Line::styled(
+                format!(
+                    "{:>7}° {:>8.3} {:>8.3} {:>8.3} {:>10.4} {:>10.2}",
+                    res.yaw_angle, res.cd, res.cl, res.cs, res.cda, res.fd
+                ),
+                STYLE_ACCENT,
+            ));
+        }
+    }
+
+    /// Where the value of `key` comes from, shown next to it.
+    fn source(sim: &SimulationParams, key: &str) -> Span<'a> {
+        Span::styled(
+            format!(
+                "  ({})",
+                sim.sources.get(key).map_or("default", String::as_str)
+            ),
+            STYLE_DIMMED,
+        )
+    }
+
     fn general_lines(sim: &'a SimulationParams, lines: &mut Vec<Line<'a>>) {
         if !sim.selected {
             lines.push(Line::from(vec![Span::styled(
@@ -107,6 +194,7 @@ impl<'a> SimulationDetail<'a> {
         lines.push(Line::from(vec![
             Span::styled("Revision: ", STYLE_BOLD),
             Span::styled(sim.params.revision_or_placeholder(), STYLE_ACCENT),
+            Self::source(sim, "revision"),
         ]));
 
         lines.push(Line::default());
@@ -114,6 +202,7 @@ impl<'a> SimulationDetail<'a> {
         lines.push(Line::from(vec![
             Span::styled("Quality: ", STYLE_BOLD),
             Span::styled(sim.params.quality.to_string(), STYLE_ACCENT),
+            Self::source(sim, "quality"),
         ]));
 
         lines.push(Line::default());
@@ -128,6 +217,7 @@ impl<'a> SimulationDetail<'a> {
                     .join(", "),
                 STYLE_ACCENT,
             ),
+            Self::source(sim, "yaw_angles"),
         ]));
 
         lines.push(Line::default());
@@ -135,25 +225,31 @@ impl<'a> SimulationDetail<'a> {
         lines.push(Line::from(vec![
             Span::styled("Fluid: ", STYLE_BOLD),
             Span::styled(sim.params.fluid.to_string(), STYLE_ACCENT),
+            Self::source(sim, "fluid"),
         ]));
         lines.push(Line::from(vec![
             Span::styled("Speed: ", STYLE_BOLD),
             Span::styled(format!("{} m/s", sim.params.fluid_speed), STYLE_ACCENT),
+            Self::source(sim, "fluid_speed"),
         ]));
 
         lines.push(Line::default());
 
+        Self::boundary_lines(sim, lines);
+    }
+
+    fn boundary_lines(sim: &'a SimulationParams, lines: &mut Vec<Line<'a>>) {
         if let Some((boundary, offset, is_moving)) =
             match (sim.params.has_ground, &sim.params.ceiling) {
                 (true, _) => Some((
                     "ground",
-                    sim.params.ground_offset.0,
-                    sim.params.is_ground_moving,
+                    (sim.params.ground_offset.0, "ground_offset"),
+                    (sim.params.is_ground_moving, "is_ground_moving"),
                 )),
                 (_, Some(Ceiling { offset, condition })) => Some((
                     "ceiling",
-                    offset.0,
-                    *condition == CeilingCondition::Moving,
+                    (offset.0, "ceiling"),
+                    (*condition == CeilingCondition::Moving, "ceiling"),
                 )),
                 _ => None,
             }
@@ -161,20 +257,31 @@ impl<'a> SimulationDetail<'a> {
             lines.push(Line::from(vec![
                 Span::styled("Boundary: ", STYLE_BOLD),
                 Span::styled(boundary, STYLE_ACCENT),
+                Self::source(
+                    sim,
+                    if sim.params.has_ground {
+                        "has_ground"
+                    } else {
+                        "ceiling"
+                    },
+                ),
             ]));
 
             lines.push(Line::from(vec![
                 Span::styled("Offset: ", STYLE_BOLD),
-                Span::styled(format!("{offset} m"), STYLE_ACCENT),
+                Span::styled(format!("{} m", offset.0), STYLE_ACCENT),
+                Self::source(sim, offset.1),
             ]));
             lines.push(Line::from(vec![
                 Span::styled("Moving: ", STYLE_BOLD),
-                Span::styled(bool_to_human(is_moving), STYLE_ACCENT),
+                Span::styled(bool_to_human(is_moving.0), STYLE_ACCENT),
+                Self::source(sim, is_moving.1),
             ]));
         } else {
             lines.push(Line::from(vec![
                 Span::styled("Boundary: ", STYLE_BOLD),
                 Span::styled("no", STYLE_ACCENT),
+                Self::source(sim, "has_ground"),
             ]));
         }
 
@@ -187,6 +294,7 @@ impl<'a> SimulationDetail<'a> {
                     fmt::human_boundary_layer_treatment(v),
                     STYLE_ACCENT,
                 ),
+                Self::source(sim, "boundary_layer_treatment"),
             ]));
         }
     }
@@ -476,6 +584,8 @@ impl StatefulWidget for &SimulationDetail<'_> {
 
         let mut lines = Vec::with_capacity(10);
 
+        SimulationDetail::reload_error(sim, &mut lines);
+
         SimulationDetail::submission_state(sim, &mut lines);
 
         SimulationDetail::general_lines(sim, &mut lines);
@@ -1,14 +1,26 @@
-use crate::aerocloud::{
-    Client,
-    extra_types::{CreateSimulationV7ParamsFromJson, FileV7ParamsFromJson},
-    types::{
-        CreateModelV7Params, CreateModelV7ParamsFilesItem,
-        CreateSimulationV7Params, Filename, Id, ModelV7, Url,
+use crate::{
+    aerocloud::{
+        Client,
+        extra_types::{CreateSimulationV7ParamsFromJson, FileV7ParamsFromJson},
+        schema::{self, Definition},
+        types::{
+            CreateModelV7Params, CreateModelV7ParamsFilesItem,
+            CreateSimulationV7Params, Filename, Id, ModelV7, SimulationStatus,
+            SimulationV7, Url,
+        },
     },
+    commands::aerocloud::v7::batch::discovery::Discovery,
+    config::project::{self, SimulationDefaults, merge},
+    git,
+    params_file::{self, ParamsFormat, Problem},
 };
 use bytesize::ByteSize;
 use color_eyre::eyre::{self, WrapErr};
-use std::path::{Path, PathBuf};
+use serde_json::{Map, Value, json};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 use tokio::fs;
 use uuid::Uuid;
 
@@ -25,7 +37,7 @@ pub enum SubmissionState {
 }
 
 impl SubmissionState {
-    const FILENAME: &str = "submission_state.json";
+    pub const FILENAME: &str = "submission_state.json";
 
     pub async fn from_dir_or_default(dir: &Path) -> Self {
         if let Ok(buf) = fs::read(dir.join(Self::FILENAME)).await
@@ -37,6 +49,37 @@ impl SubmissionState {
         }
     }
 
+    /// Whether anything else in `dir` was modified after the state was written.
+    async fn is_outdated(dir: &Path) -> bool {
+        let Ok(written_at) = fs::metadata(dir.join(Self::FILENAME))
+            .await
+            .and_then(|metadata| metadata.modified())
+        else {
+            return false;
+        };
+
+        let Ok(mut dir_stream) = fs::read_dir(dir).await else {
+            return false;
+        };
+
+        while let Ok(Some(entry)) = dir_stream.next_entry().await {
+            if entry.file_name() == Self::FILENAME {
+                continue;
+            }
+
+            if let Ok(modified) = entry
+                .metadata()
+                .await
+                .and_then(|metadata| metadata.modified())
+                && modified > written_at
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+
     pub async fn write(&self, dir: &Path) -> eyre::Result<()> {
         fs::write(dir.join(Self::FILENAME), &serde_json::to_vec(self)?).await?;
         Ok(())
@@ -64,7 +107,12 @@ impl ModelParams {
         }
     }
 
-    async fn from_dir(dir: &Path) -> eyre::Result<Self> {
+    /// Problems found in file params are added to `problems`, leaving defaults
+    /// in place of those params.
+    async fn from_dir(
+        dir: &Path,
+        problems: &mut Vec<Problem>,
+    ) -> eyre::Result<Self> {
         let mut files = vec![];
 
         let mut dir_stream = fs::read_dir(dir)
@@ -82,9 +130,8 @@ impl ModelParams {
                 continue;
             }
 
-            if let Some("json") =
-                path.extension().and_then(|os_str| os_str.to_str())
-            {
+            // NOTE: params files, whatever their format, aren't model files.
+            if ParamsFormat::from_path(&path).is_some() {
                 continue;
             }
 
@@ -102,20 +149,35 @@ impl ModelParams {
                 continue;
             };
 
-            let file_params_path = path.with_extension("json");
-
-            let file_params = if file_params_path.exists() {
-                serde_json::from_slice(
-                    &fs::read(&file_params_path).await.wrap_err_with(|| {
-                        format!("failed to read `{}`", file_params_path.display())
-                    })?,
-                )
-                .wrap_err_with(|| {
-                    format!("failed to parse `{}`", file_params_path.display())
-                })?
-            } else {
-                FileV7ParamsFromJson::default()
-            };
+            let (file_params_path, file_params) =
+                match ParamsFormat::find(&path.with_extension(""))? {
+                    Some(file_params_path) => {
+                        let found = problems.len();
+                        let value = read_params(
+                            &file_params_path,
+                            Definition::File,
+                            problems,
+                        )
+                        .await?;
+
+                        let file_params = if problems.len() > found {
+                            FileV7ParamsFromJson::default()
+                        } else {
+                            serde_json::from_value(value).wrap_err_with(|| {
+                                format!(
+                                    "failed to parse `{}`",
+                                    file_params_path.display()
+                                )
+                            })?
+                        };
+
+                        (file_params_path, file_params)
+                    }
+                    None => (
+                        path.with_extension("json"),
+                        FileV7ParamsFromJson::default(),
+                    ),
+                };
 
             file_params.ensure_is_valid().wrap_err_with(|| {
                 eyre::eyre!(
@@ -136,11 +198,30 @@ impl ModelParams {
                 filename,
                 size,
                 params: file_params,
+                params_path: file_params_path,
             });
         }
 
         files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
 
+        // NOTE: no files at all is fine here, the model may be an existing one.
+        if !files.is_empty() {
+            let names = files
+                .iter()
+                .map(|file| json!({ "path": file.filename }))
+                .collect::<Vec<_>>();
+
+            problems.extend(
+                schema::validate(&json!({ "files": names }), Definition::Model)
+                    .into_iter()
+                    .map(|violation| Problem {
+                        file: dir.display().to_string(),
+                        position: None,
+                        violation,
+                    }),
+            );
+        }
+
         Ok(Self::New { files })
     }
 
@@ -155,109 +236,223 @@ impl ModelParams {
     }
 }
 
+/// How params missing from simulation dirs are filled in.
+#[derive(Debug, Clone, Default)]
+pub struct ParamsFill {
+    pub defaults: SimulationDefaults,
+    /// Format of the revision read from git, see [`git::Commit::format`].
+    pub git_revision: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct SimulationParams {
     pub internal_id: Uuid,
     pub dir: PathBuf,
+    /// Where own params are read from, which may not exist yet.
+    pub params_path: PathBuf,
     pub params: CreateSimulationV7ParamsFromJson,
     pub model_params: ModelParams,
 
     pub selected: bool,
     pub submission_state: SubmissionState,
+    /// Last fetched state of the simulation once sent.
+    pub remote: Option<Box<SimulationV7>>,
+    /// Whether files in the dir were modified after the simulation was sent.
+    pub changed_after_sent: bool,
+    pub sources: ParamsSources,
+    /// Why the dir failed to reload after changing, leaving the params above
+    /// outdated until fixed.
+    pub reload_error: Option<String>,
 }
 
+/// Where the value of each top-level param comes from, those missing are left to
+/// the API.
+pub type ParamsSources = BTreeMap<String, String>;
+
 impl SimulationParams {
-    pub async fn many_from_root_dir(
+    /// File name, sans extension, of the params of a simulation, in any of the
+    /// [`ParamsFormat`]s.
+    pub const PARAMS_STEM: &str = "params";
+
+    /// Merged beneath the params, from the simulation dir up to the root one.
+    pub const DEFAULTS_STEM: &str = "defaults";
+
+    pub async fn discover(
         client: &Client,
-        root_dir: &Path,
+        fill: &ParamsFill,
+        discovery: &Discovery,
     ) -> eyre::Result<Vec<Self>> {
-        if !fs::metadata(root_dir).await?.is_dir() {
-            eyre::bail!("`{}` is not a directory", root_dir.display());
+        let mut sims_params = vec![];
+
+        for dir in discovery.sim_dirs().await? {
+            sims_params.push(
+                Self::from_dir(client, fill, discovery, &dir)
+                    .await
+                    .wrap_err_with(|| {
+                        format!(
+                            "failed to build simulation params from dir `{}`",
+                            dir.display()
+                        )
+                    })?,
+            );
         }
 
-        let mut sims_params = vec![];
+        Ok(sims_params)
+    }
 
-        let mut dir_stream =
-            fs::read_dir(root_dir).await.wrap_err_with(|| {
-                eyre::eyre!("error listing root dir `{}`", root_dir.display())
-            })?;
+    #[allow(clippy::too_many_lines)]
+    pub async fn from_dir(
+        client: &Client,
+        fill: &ParamsFill,
+        discovery: &Discovery,
+        dir: &Path,
+    ) -> eyre::Result<Self> {
+        let params_path = ParamsFormat::find(&dir.join(Self::PARAMS_STEM))?;
+        let sim_name = discovery.relative_name(dir)?;
+
+        let mut problems = vec![];
+
+        let own = match &params_path {
+            Some(path) => {
+                let format = ParamsFormat::from_path(path).unwrap_or_default();
+                let buf = fs::read(path).await.wrap_err_with(|| {
+                    format!("failed to read `{}`", path.display())
+                })?;
+                let value: Value = parse_params(path, format, &buf)?;
 
-        while let Some(entry) = dir_stream
-            .next_entry()
-            .await
-            .wrap_err("iterating root dir dir stream")?
+                problems.extend(format.validate(
+                    path,
+                    &buf,
+                    &value,
+                    Definition::Simulation,
+                ));
+
+                Some((path, format, buf, value))
+            }
+            None => None,
+        };
+
+        // NOTE: closest defaults first, so that they win over farther ones.
+        let mut layers = vec![];
+        let mut defaults_paths = vec![];
+
+        for ancestor in dir
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(&discovery.root_dir))
         {
-            let path = entry.path();
+            if let Some(path) =
+                ParamsFormat::find(&ancestor.join(Self::DEFAULTS_STEM))?
+            {
+                let defaults =
+                    read_params(&path, Definition::Simulation, &mut problems)
+                        .await?;
 
-            if !path.is_dir() {
-                continue;
+                layers
+                    .push((discovery.relative_name(&path)?, into_map(defaults)));
+                defaults_paths.push(path);
             }
+        }
 
-            sims_params.push(Self::from_dir(client, &path).await.wrap_err_with(
-                || {
-                    format!(
-                        "failed to build simulation params from dir `{}`",
-                        path.display()
-                    )
-                },
-            )?);
+        if !fill.defaults.0.is_empty() {
+            layers.push((project::FILENAME.to_owned(), fill.defaults.0.clone()));
         }
 
-        Ok(sims_params)
-    }
+        // NOTE: an existing model is fetched once params are known, local
+        // files are not part of the simulation then.
+        let reuses_model = own
+            .iter()
+            .map(|(_, _, _, value)| value.get("model_id"))
+            .chain(layers.iter().map(|(_, defaults)| defaults.get("model_id")))
+            .flatten()
+            .next()
+            .is_some_and(|model_id| !model_id.is_null());
+
+        let local_model_params = if reuses_model {
+            None
+        } else {
+            Some(ModelParams::from_dir(dir, &mut problems).await?)
+        };
 
-    #[allow(clippy::too_many_lines)]
-    pub async fn from_dir(client: &Client, dir: &Path) -> eyre::Result<Self> {
-        let params_path = dir.join("params.json");
-
-        let dir_name = dir.file_name().ok_or_else(|| {
-            eyre::eyre!("no file name for path `{}`", dir.display())
-        })?;
-        let sim_name = dir_name
-            .to_str()
-            .ok_or_else(|| {
-                eyre::eyre!(
-                    "dir name {:?} contains invalid utf-8 characters",
-                    dir_name
-                )
-            })?
-            .to_owned();
+        params_file::ensure_no_problems(&problems)?;
 
-        let params = if params_path.exists() {
-            let buf = fs::read(&params_path).await.wrap_err_with(|| {
-                format!("failed to read `{}`", params_path.display())
-            })?;
+        let mut merged = Map::new();
+        let mut sources = ParamsSources::new();
 
-            let mut params: CreateSimulationV7ParamsFromJson =
-                serde_json::from_slice(&buf).wrap_err_with(|| {
-                    format!("failed to parse `{}`", params_path.display())
-                })?;
+        if let Some((path, _, _, value)) = &own {
+            merged = into_map(value.clone());
 
-            params.name = sim_name;
+            let source = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
 
-            params
-        } else {
-            CreateSimulationV7ParamsFromJson {
-                name: sim_name,
-                ..Default::default()
+            sources
+                .extend(merged.keys().map(|key| (key.clone(), source.clone())));
+        }
+
+        for (source, defaults) in &layers {
+            for key in defaults.keys() {
+                sources.entry(key.clone()).or_insert_with(|| source.clone());
+            }
+
+            merge(&mut merged, defaults);
+        }
+
+        // NOTE: parse straight from the file when there's nothing to merge, so
+        // that errors keep their position in it.
+        let mut params: CreateSimulationV7ParamsFromJson = match &own {
+            Some((path, format, buf, _)) if layers.is_empty() => {
+                parse_params(path, *format, buf)?
             }
+            _ => serde_json::from_value(Value::Object(merged)).wrap_err_with(
+                || {
+                    format!(
+                        "failed to build params of `{}` out of its params and \
+                         defaults",
+                        dir.display(),
+                    )
+                },
+            )?,
         };
 
-        let model_params = if let Some(model_id) = &params.model_id {
-            ModelParams::from_existing(client, model_id).await?
-        } else {
-            ModelParams::from_dir(dir).await?
+        params.name = sim_name;
+        sources.remove("name");
+
+        if params.revision.is_none()
+            && let Some(format) = &fill.git_revision
+        {
+            params.revision =
+                Some(git::revision(dir, &defaults_paths, format).await?);
+            sources.insert("revision".into(), "git".into());
+        }
+
+        let model_params = match (&params.model_id, local_model_params) {
+            (Some(model_id), _) => {
+                ModelParams::from_existing(client, model_id).await?
+            }
+            (None, Some(local_model_params)) => local_model_params,
+            (None, None) => ModelParams::New { files: vec![] },
         };
 
         let submission_state = SubmissionState::from_dir_or_default(dir).await;
+        let changed_after_sent =
+            matches!(submission_state, SubmissionState::Sent { .. })
+                && SubmissionState::is_outdated(dir).await;
 
         Ok(Self {
             internal_id: Uuid::new_v4(),
             dir: dir.into(),
+            params_path: params_path.unwrap_or_else(|| {
+                dir.join(Self::PARAMS_STEM).with_extension("json")
+            }),
             params,
             model_params,
             selected: true,
             submission_state,
+            remote: None,
+            changed_after_sent,
+            sources,
+            reload_error: None,
         })
     }
 
@@ -281,12 +476,43 @@ impl SimulationParams {
         state: SubmissionState,
     ) -> eyre::Result<()> {
         self.submission_state = state;
+        self.remote = None;
+        self.changed_after_sent = false;
         self.submission_state.write(&self.dir).await?;
         Ok(())
     }
 
+    pub fn sent_id(&self) -> Option<&Id> {
+        if let SubmissionState::Sent { id, .. } = &self.submission_state {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the simulation was sent and might still change remotely.
+    pub fn needs_polling(&self) -> bool {
+        self.sent_id().is_some()
+            && self.remote.as_ref().is_none_or(|sim| {
+                matches!(
+                    sim.status,
+                    SimulationStatus::Progress | SimulationStatus::Draft
+                )
+            })
+    }
+
+    /// Keeps what was fetched for `prev` if it refers to the same simulation.
+    pub fn keep_remote(&mut self, prev: &Self) {
+        if let (Some(id), Some(prev_id)) = (self.sent_id(), prev.sent_id())
+            && id.0 == prev_id.0
+        {
+            self.remote.clone_from(&prev.remote);
+        }
+    }
+
     pub fn is_submittable(&self) -> bool {
         self.selected
+            && self.reload_error.is_none()
             && self.model_params.is_submittable()
             && matches!(
                 self.submission_state,
@@ -360,4 +586,42 @@ pub struct FileParams {
     pub filename: Filename,
     pub size: ByteSize,
     pub params: FileV7ParamsFromJson,
+    /// Where `params` are read from, which may not exist yet.
+    pub params_path: PathBuf,
+}
+
+/// Reads params at `path` as they are, adding problems found in them to
+/// `problems`.
+async fn read_params(
+    path: &Path,
+    definition: Definition,
+    problems: &mut Vec<Problem>,
+) -> eyre::Result<Value> {
+    let buf = fs::read(path)
+        .await
+        .wrap_err_with(|| format!("failed to read `{}`", path.display()))?;
+    let format = ParamsFormat::from_path(path).unwrap_or_default();
+    let value = parse_params(path, format, &buf)?;
+
+    problems.extend(format.validate(path, &buf, &value, definition));
+
+    Ok(value)
+}
+
+/// Params are known to be a map once validated.
+fn into_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+fn parse_params<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    format: ParamsFormat,
+    buf: &[u8],
+) -> eyre::Result<T> {
+    format
+        .parse(buf)
+        .wrap_err_with(|| format!("failed to parse `{}`", path.display()))
 }
@@ -0,0 +1,85 @@
+use crate::commands::aerocloud::v7::batch::{
+    Event, simulation_params::SubmissionState,
+};
+use color_eyre::eyre::{self, WrapErr};
+use notify_debouncer_mini::{
+    DebounceEventResult, Debouncer, new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode},
+};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+/// How long the filesystem has to be quiet before changes are reported.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Stops watching once dropped.
+pub type Watcher = Debouncer<RecommendedWatcher>;
+
+/// Watches `root_dir` recursively, sending the paths that changed within it,
+/// whether they still exist or not.
+pub fn watch_root_dir(
+    root_dir: &Path,
+    tx: mpsc::Sender<Event>,
+) -> eyre::Result<Watcher> {
+    // NOTE: events carry canonical paths, while simulation dirs are built from
+    // `root_dir` as given.
+    let canonical_root = root_dir.canonicalize().wrap_err_with(|| {
+        format!("resolving root dir `{}`", root_dir.display())
+    })?;
+
+    let mut debouncer = new_debouncer(DEBOUNCE, {
+        let root_dir = root_dir.to_owned();
+        let canonical_root = canonical_root.clone();
+
+        move |res: DebounceEventResult| match res {
+            Ok(events) => {
+                let paths: Vec<PathBuf> = events
+                    .iter()
+                    .filter_map(|event| {
+                        relative_to_root(&root_dir, &canonical_root, &event.path)
+                    })
+                    .collect();
+
+                if !paths.is_empty() {
+                    let _ = tx.blocking_send(Event::FilesChanged(paths));
+                }
+            }
+            Err(err) => {
+                tracing::warn!("failed to watch root dir: {err:?}");
+            }
+        }
+    })
+    .wrap_err("starting filesystem watcher")?;
+
+    debouncer
+        .watcher()
+        .watch(&canonical_root, RecursiveMode::Recursive)
+        .wrap_err_with(|| {
+            format!("watching root dir `{}`", root_dir.display())
+        })?;
+
+    Ok(debouncer)
+}
+
+/// `path` as if it was found walking `root_dir`, ignoring changes made by the
+/// batch itself.
+fn relative_to_root(
+    root_dir: &Path,
+    canonical_root: &Path,
+    path: &Path,
+) -> Option<PathBuf> {
+    if path.file_name() == Some(SubmissionState::FILENAME.as_ref()) {
+        return None;
+    }
+
+    let relative = path.strip_prefix(canonical_root).ok()?;
+
+    if relative.as_os_str().is_empty() {
+        return None;
+    }
+
+    Some(root_dir.join(relative))
+}
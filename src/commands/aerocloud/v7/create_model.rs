@@ -1,6 +1,7 @@
 use crate::{
     aerocloud::{
         Client, fmt_progenitor_err, new_idempotency_key,
+        schema::{self, Definition},
         types::{
             CreateModelV7Params, CreateModelV7ParamsFilesItem, FileUnit, Id,
             ModelV7, ModelV7FilesItem, Quaternion, UpdatePartV7Params,
@@ -8,12 +9,17 @@ use crate::{
     },
     args::Args,
     http::UPLOAD_REQ_TIMEOUT,
+    params_file::{self, ParamsFormat},
 };
 use color_eyre::eyre::{self, WrapErr, bail};
 use itertools::Itertools;
 use progenitor_client::ClientInfo;
 use reqwest::header::CONTENT_LENGTH;
-use std::{collections::HashMap, path::PathBuf};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 use tokio::{
     fs::{self, File as AsyncFile},
     task::JoinSet,
@@ -81,11 +87,32 @@ impl TryInto<CreateModelV7Params> for CreateModelParams {
     }
 }
 
-pub async fn run(args: &Args, client: &Client, params: &str) -> eyre::Result<()> {
+pub async fn run(
+    args: &Args,
+    client: &Client,
+    params_path: Option<&Path>,
+    params: &str,
+) -> eyre::Result<()> {
     let idempotency_key = new_idempotency_key();
 
-    let params: CreateModelParams =
-        serde_json::from_str(params).wrap_err("failed to parse json")?;
+    let format = params_path
+        .and_then(ParamsFormat::from_path)
+        .unwrap_or_default();
+
+    let value: Value = format
+        .parse(params.as_bytes())
+        .wrap_err_with(|| format!("failed to parse {format}"))?;
+
+    validate_params(
+        params_path.unwrap_or_else(|| Path::new(params_file::STDIN)),
+        format,
+        params.as_bytes(),
+        &value,
+    )?;
+
+    let params: CreateModelParams = format
+        .parse(params.as_bytes())
+        .wrap_err_with(|| format!("failed to parse {format}"))?;
 
     validate_files(&params.files).await?;
 
@@ -265,6 +292,48 @@ async fn upload_file(
     Ok(())
 }
 
+/// Checks params against the schema, along with the name files would be sent
+/// with, reporting every problem at once.
+fn validate_params(
+    path: &Path,
+    format: ParamsFormat,
+    buf: &[u8],
+    value: &Value,
+) -> eyre::Result<()> {
+    let mut problems = format.validate(path, buf, value, Definition::Model);
+
+    let files = value
+        .get("files")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+
+    for (idx, file) in files.enumerate() {
+        let Some(filename) = file
+            .get("path")
+            .and_then(Value::as_str)
+            .and_then(|path| Path::new(path).file_name())
+        else {
+            continue;
+        };
+
+        let violations = schema::validate(
+            &filename.to_string_lossy().into(),
+            Definition::Filename,
+        )
+        .into_iter()
+        .map(|violation| schema::Violation {
+            pointer: format!("/files/{idx}/path"),
+            ..violation
+        })
+        .collect();
+
+        problems.extend(format.locate_violations(path, buf, violations));
+    }
+
+    params_file::ensure_no_problems(&problems)
+}
+
 async fn validate_files(files: &[CreateModelFileParams]) -> eyre::Result<()> {
     for file in files {
         let attr = fs::metadata(&file.path).await.with_context(|| {
@@ -1,24 +1,56 @@
 use crate::{
     aerocloud::{
         Client, fmt_progenitor_err, new_idempotency_key,
+        schema::Definition,
         types::{CreateSimulationV7Params, Id},
     },
     args::Args,
+    config::Config,
+    daemon,
     fmt::link,
+    git,
+    params_file::{self, ParamsFormat},
 };
 use color_eyre::eyre::{self, WrapErr};
+use serde_json::Value;
+use std::path::Path;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     args: &Args,
+    config: &Config,
     client: &Client,
     model_id: Option<Id>,
     project_id: Option<Id>,
+    params_path: Option<&Path>,
     params: &str,
+    git_revision: Option<&str>,
 ) -> eyre::Result<()> {
     let idempotency_key = new_idempotency_key();
 
-    let mut params = serde_json::from_str::<CreateSimulationV7Params>(params)
-        .wrap_err("failed to parse json")?;
+    let mut defaults = config.project.simulation.clone();
+    if let Some(id) = &config.project.project_id {
+        defaults = defaults.with("project_id", id.to_string());
+    }
+
+    let format = params_path
+        .and_then(ParamsFormat::from_path)
+        .unwrap_or_default();
+
+    let value: Value = format
+        .parse(params.as_bytes())
+        .wrap_err_with(|| format!("failed to parse {format}"))?;
+
+    params_file::ensure_no_problems(&format.validate(
+        params_path.unwrap_or_else(|| Path::new(params_file::STDIN)),
+        params.as_bytes(),
+        &value,
+        Definition::Simulation,
+    ))?;
+
+    let mut params = defaults
+        .parse::<CreateSimulationV7Params>(format, params.as_bytes())
+        .wrap_err_with(|| format!("failed to parse {format}"))?;
 
     if let Some(id) = model_id {
         params.model_id = id;
@@ -26,6 +58,17 @@ pub async fn run(
     if let Some(id) = project_id {
         params.project_id = id;
     }
+    if params.revision.is_none()
+        && let Some(format) = git_revision
+    {
+        // NOTE: relative to the current dir when reading from stdin.
+        let dir = params_path
+            .and_then(Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+
+        params.revision = Some(git::revision(dir, &[], format).await?);
+    }
 
     let sim = client
         .simulations_v7_create(&idempotency_key, &params)
@@ -33,7 +76,11 @@ pub async fn run(
         .map_err(fmt_progenitor_err)?
         .into_inner();
 
-    if args.json {
+    daemon::track_if_running(args.daemon_addr, vec![sim.id.clone()]).await;
+
+    if let Some(template) = &args.template {
+        template.print(&sim)?;
+    } else if args.json {
         println!("{}", serde_json::to_string(&sim)?);
     } else {
         println!(
@@ -1,27 +1,39 @@
 use crate::{
-    aerocloud::{Client, types::Id},
+    aerocloud::{Client, cache::Cache, types::Id},
     args::Args,
 };
 use color_eyre::eyre;
 use serde_json::json;
 
-pub async fn run(args: &Args, client: &Client, ids: &[Id]) -> eyre::Result<()> {
+pub async fn run(
+    args: &Args,
+    client: &Client,
+    cache: &Cache,
+    ids: &[Id],
+) -> eyre::Result<()> {
+    let mut deleted = vec![];
+
     for id in ids {
-        if let Err(err) = run_one(args, client, id).await {
-            if args.json {
-                println!(
-                    "{}",
-                    &serde_json::to_string(&json!({
-                        "error": format!("{}", err),
-                        "simulation_id": id,
-                    }))?
-                );
-            } else {
-                println!("Failed to delete simulation with id {id}: {err}");
+        match run_one(args, client, id).await {
+            Ok(()) => deleted.push(id.clone()),
+            Err(err) => {
+                if args.json {
+                    println!(
+                        "{}",
+                        &serde_json::to_string(&json!({
+                            "error": format!("{}", err),
+                            "simulation_id": id,
+                        }))?
+                    );
+                } else {
+                    println!("Failed to delete simulation with id {id}: {err}");
+                }
             }
         }
     }
 
+    cache.evict(&deleted).await?;
+
     Ok(())
 }
 
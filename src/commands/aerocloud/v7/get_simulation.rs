@@ -0,0 +1,98 @@
+use crate::{
+    aerocloud::{
+        Client, fmt,
+        results::{Processing, UnitSystem},
+        types::{Id, SimulationV7},
+    },
+    args::Args,
+    fmt::{NOT_AVAILABLE, link},
+    utils::new_dynamic_table,
+};
+use chrono::Local;
+use color_eyre::eyre;
+use itertools::Itertools;
+
+pub async fn run(
+    args: &Args,
+    client: &Client,
+    simulation_id: &Id,
+    units: UnitSystem,
+) -> eyre::Result<()> {
+    let sim = client.simulations_v7_get(simulation_id).await?.into_inner();
+    let processing = Processing {
+        units,
+        ..Processing::default()
+    };
+
+    if let Some(template) = &args.template {
+        template.print(&processing.with_processed(&sim))?;
+    } else if args.json {
+        println!(
+            "{}",
+            serde_json::to_string(&processing.with_processed(&sim))?
+        );
+    } else {
+        print_human(&sim, &processing);
+    }
+
+    Ok(())
+}
+
+fn print_human(sim: &SimulationV7, processing: &Processing) {
+    let mut table = new_dynamic_table();
+    table
+        .set_header(vec!["Key", "Value"])
+        .add_row(vec!["Id".into(), sim.id.to_string()])
+        .add_row(vec!["Name".into(), sim.name.clone()])
+        .add_row(vec![
+            "Revision".into(),
+            sim.revision.clone().unwrap_or_else(|| NOT_AVAILABLE.into()),
+        ])
+        .add_row(vec![
+            "Status".into(),
+            fmt::human_simulation_status(sim.status).to_owned(),
+        ])
+        .add_row(vec!["Quality".into(), sim.params.quality.to_string()])
+        .add_row(vec![
+            "Yaw angle(s)".into(),
+            sim.params
+                .yaw_angles
+                .iter()
+                .map(|v| format!("{v}°"))
+                .join(", "),
+        ])
+        .add_row(vec![
+            "Fluid & Speed".into(),
+            format!("{}, {} m/s", sim.params.fluid, sim.params.fluid_speed),
+        ])
+        .add_row(vec!["Boundary".into(), fmt::human_boundary(&sim.params)])
+        .add_row(vec![
+            "Created at".into(),
+            sim.created_at.with_timezone(&Local).to_string(),
+        ])
+        .add_row(vec!["Link".into(), link(&sim.browser_url)]);
+
+    println!("{table}");
+
+    let Some(results) = &sim.results else {
+        return;
+    };
+
+    let mut table = new_dynamic_table();
+    table.set_header(
+        std::iter::once("Yaw angle".to_owned()).chain(processing.header()),
+    );
+
+    for res in &results.yaw_angles {
+        table.add_row(
+            std::iter::once(format!("{}°", res.yaw_angle))
+                .chain(processing.row(sim.params.fluid_speed.0, res)),
+        );
+    }
+
+    for col in table.column_iter_mut() {
+        col.set_cell_alignment(comfy_table::CellAlignment::Right);
+    }
+
+    println!("{table}");
+}
@@ -1,7 +1,8 @@
 use crate::{
     aerocloud::{
         Client,
-        types::{ListPageProjectsV7, PaginationOffset, ProjectStatus, ProjectV7},
+        cache::Cache,
+        types::{ListPageProjectsV7, ProjectStatus, ProjectV7},
     },
     args::Args,
     fmt::link,
@@ -13,27 +14,27 @@ use color_eyre::eyre;
 pub async fn run(
     args: &Args,
     client: &Client,
+    cache: &Cache,
     status: Option<ProjectStatus>,
 ) -> eyre::Result<()> {
-    let mut all_items = vec![];
-    let mut offset = PaginationOffset(0u64);
+    let mut all_items = cache
+        .projects(|offset| async move {
+            let ListPageProjectsV7 { items, nav } = client
+                .projects_v7_list(None, Some(&offset), None)
+                .await?
+                .into_inner();
 
-    loop {
-        let ListPageProjectsV7 { items, nav } = client
-            .projects_v7_list(None, Some(&offset), status)
-            .await?
-            .into_inner();
+            Ok((items, nav))
+        })
+        .await?;
 
-        all_items.extend(items);
-
-        if let Some(next_offset) = nav.next_offset {
-            offset = PaginationOffset(next_offset);
-        } else {
-            break;
-        }
+    if let Some(status) = status {
+        all_items.retain(|project| project.status == status);
     }
 
-    if args.json {
+    if let Some(template) = &args.template {
+        template.print_all(&all_items)?;
+    } else if args.json {
         println!("{}", &serde_json::to_string(&all_items)?);
     } else {
         print_human(&all_items);
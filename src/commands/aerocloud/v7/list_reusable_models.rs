@@ -1,7 +1,8 @@
 use crate::{
     aerocloud::{
         Client,
-        types::{ListPageModelsV7, ModelV7, PaginationOffset},
+        cache::Cache,
+        types::{ListPageModelsV7, ModelV7},
     },
     args::Args,
     utils::new_dynamic_table,
@@ -9,26 +10,25 @@ use crate::{
 use chrono::Local;
 use color_eyre::eyre;
 
-pub async fn run(args: &Args, client: &Client) -> eyre::Result<()> {
-    let mut all_items = vec![];
-    let mut offset = PaginationOffset(0u64);
-
-    loop {
-        let ListPageModelsV7 { items, nav } = client
-            .models_v7_list_reusable(None, Some(&offset))
-            .await?
-            .into_inner();
-
-        all_items.extend(items);
-
-        if let Some(next_offset) = nav.next_offset {
-            offset = PaginationOffset(next_offset);
-        } else {
-            break;
-        }
-    }
-
-    if args.json {
+pub async fn run(
+    args: &Args,
+    client: &Client,
+    cache: &Cache,
+) -> eyre::Result<()> {
+    let all_items = cache
+        .reusable_models(|offset| async move {
+            let ListPageModelsV7 { items, nav } = client
+                .models_v7_list_reusable(None, Some(&offset))
+                .await?
+                .into_inner();
+
+            Ok((items, nav))
+        })
+        .await?;
+
+    if let Some(template) = &args.template {
+        template.print_all(&all_items)?;
+    } else if args.json {
         println!("{}", &serde_json::to_string(&all_items)?);
     } else {
         print_human(&all_items);
@@ -1,66 +1,87 @@
 use crate::{
     aerocloud::{
-        Client, fmt,
+        Client,
+        cache::Cache,
+        fmt,
+        results::Processing,
         types::{
-            Ceiling, CeilingCondition, FluidSpeed, Id, ListPageSimulationsV7,
-            PaginationOffset, ProjectV7, SimulationQuality,
+            FluidSpeed, Id, ListPageSimulationsV7, ProjectV7, SimulationQuality,
             SimulationResultsV7YawAnglesItem, SimulationV7,
             SimulationsV7ListStatus, YawAngle,
         },
     },
     args::Args,
-    fmt::{NOT_AVAILABLE, link},
+    filter::Filter,
+    fmt::link,
     utils::new_dynamic_table,
 };
 use chrono::Local;
 use color_eyre::eyre;
 use itertools::Itertools;
-use std::fmt::Write as _;
+
+/// Filters applied by the API when listing simulations.
+#[derive(Debug, Default)]
+pub struct Query {
+    pub status: Option<SimulationsV7ListStatus>,
+    pub quality: Option<SimulationQuality>,
+    pub fluid_speed: Option<FluidSpeed>,
+    pub yaw_angle: Option<YawAngle>,
+}
+
+impl Query {
+    /// Identifies the query in the cache, empty when nothing is filtered.
+    fn key(&self) -> String {
+        [
+            self.status.map(|v| format!("status-{v}")),
+            self.quality.map(|v| format!("quality-{v}")),
+            self.fluid_speed
+                .as_ref()
+                .map(|v| format!("fluid_speed-{v}")),
+            self.yaw_angle.as_ref().map(|v| format!("yaw_angle-{v}")),
+        ]
+        .into_iter()
+        .flatten()
+        .join("-")
+    }
+}
 
 #[allow(clippy::too_many_arguments)]
 pub async fn run(
     args: &Args,
     client: &Client,
+    cache: &Cache,
     project_id: &Id,
     show_results: bool,
-    status: Option<SimulationsV7ListStatus>,
-    quality: Option<SimulationQuality>,
-    fluid_speed: Option<FluidSpeed>,
-    yaw_angle: Option<YawAngle>,
+    query: &Query,
+    filter: Option<&Filter>,
+    processing: &Processing,
 ) -> eyre::Result<()> {
-    let mut all_items = vec![];
-    let mut offset = PaginationOffset(0u64);
-
-    loop {
-        let ListPageSimulationsV7 { items, nav } = client
-            .simulations_v7_list(
-                project_id,
-                fluid_speed.as_ref(),
-                None,
-                Some(&offset),
-                quality,
-                status,
-                yaw_angle.as_ref(),
-            )
-            .await?
-            .into_inner();
-
-        all_items.extend(items);
-
-        if let Some(next_offset) = nav.next_offset {
-            offset = PaginationOffset(next_offset);
-        } else {
-            break;
-        }
+    let mut all_items = fetch_all(client, cache, project_id, query).await?;
+
+    if let Some(filter) = filter {
+        all_items = filter.retain_matching(all_items)?;
     }
 
-    if args.json {
-        println!("{}", &serde_json::to_string(&all_items)?);
+    if args.json || args.template.is_some() {
+        let items = all_items
+            .iter()
+            .map(|sim| processing.with_processed(sim))
+            .collect_vec();
+
+        if let Some(template) = &args.template {
+            template.print_all(&items)?;
+        } else {
+            println!("{}", &serde_json::to_string(&items)?);
+        }
     } else {
-        let project = client.projects_v7_get(project_id).await?.into_inner();
+        let project = cache
+            .project(project_id, async {
+                Ok(client.projects_v7_get(project_id).await?.into_inner())
+            })
+            .await?;
 
         if show_results {
-            print_results_human(&project, &all_items);
+            print_results_human(&project, &all_items, processing);
         } else {
             print_human(&project, &all_items);
         }
@@ -69,6 +90,33 @@ pub async fn run(
     Ok(())
 }
 
+/// Lists all simulations of a project matching `query` through the cache.
+pub async fn fetch_all(
+    client: &Client,
+    cache: &Cache,
+    project_id: &Id,
+    query: &Query,
+) -> eyre::Result<Vec<SimulationV7>> {
+    cache
+        .simulations(project_id, &query.key(), |offset| async move {
+            let ListPageSimulationsV7 { items, nav } = client
+                .simulations_v7_list(
+                    project_id,
+                    query.fluid_speed.as_ref(),
+                    None,
+                    Some(&offset),
+                    query.quality,
+                    query.status,
+                    query.yaw_angle.as_ref(),
+                )
+                .await?
+                .into_inner();
+
+            Ok((items, nav))
+        })
+        .await
+}
+
 fn print_human(project: &ProjectV7, items: &[SimulationV7]) {
     println!(
         "Project results: `{}` {}",
@@ -105,42 +153,7 @@ fn print_human(project: &ProjectV7, items: &[SimulationV7]) {
                 .map(|v| format!("{v}°"))
                 .join(", "),
             format!("{}, {} m/s", sim.params.fluid, sim.params.fluid_speed),
-            if sim.params.has_ground {
-                let mut s = format!(
-                    "ground, {}",
-                    if sim.params.is_ground_moving {
-                        "moving"
-                    } else {
-                        "still"
-                    },
-                );
-
-                if sim.params.ground_offset.0 != 0.0 {
-                    let _ =
-                        write!(s, ", offset: {:.2} m", sim.params.ground_offset);
-                }
-
-                s
-            } else if let Some(Ceiling { offset, condition }) =
-                &sim.params.ceiling
-            {
-                let mut s = format!(
-                    "ceiling, {}",
-                    if *condition == CeilingCondition::Moving {
-                        "moving"
-                    } else {
-                        "still"
-                    },
-                );
-
-                if offset.0 != 0.0 {
-                    let _ = write!(s, ", offset: {:.2} m", offset.0);
-                }
-
-                s
-            } else {
-                NOT_AVAILABLE.into()
-            },
+            fmt::human_boundary(&sim.params),
             sim.params
                 .boundary_layer_treatment
                 .map(fmt::human_boundary_layer_treatment)
@@ -154,7 +167,11 @@ fn print_human(project: &ProjectV7, items: &[SimulationV7]) {
     println!("{table}");
 }
 
-fn print_results_human(project: &ProjectV7, items: &[SimulationV7]) {
+fn print_results_human(
+    project: &ProjectV7,
+    items: &[SimulationV7],
+    processing: &Processing,
+) {
     println!(
         "Project results: `{}` {}",
         project.name,
@@ -178,50 +195,23 @@ fn print_results_human(project: &ProjectV7, items: &[SimulationV7]) {
     }
 
     let mut table = new_dynamic_table();
-    table.set_header(vec![
-        "Name",
-        "Quality",
-        "Yaw angle",
-        "Fluid & Speed",
-        "Surface",
-        "Fd",
-        "Fl",
-        "Fs",
-        "Cd",
-        "Cl",
-        "Cs",
-        "Cda",
-        "Cla",
-        "Csa",
-        "Mr",
-        "My",
-        "Mp",
-        "Heat transfer",
-        "Heat transfer coeff",
-    ]);
+    let mut header: Vec<String> =
+        ["Name", "Quality", "Yaw angle", "Fluid & Speed"]
+            .into_iter()
+            .map(Into::into)
+            .collect();
+    header.extend(processing.header());
+    table.set_header(header);
 
     for (sim, res) in items {
-        table.add_row(vec![
+        let mut row = vec![
             format!("{}", sim.name),
             format!("{}", sim.params.quality),
             format!("{}°", res.yaw_angle),
             format!("{}, {} m/s", sim.params.fluid, sim.params.fluid_speed),
-            format!("{:.2} m²", res.surface_area),
-            format!("{:.2} N", res.fd),
-            format!("{:.2} N", res.fl),
-            format!("{:.2} N", res.fs),
-            format!("{:.2}", res.cd),
-            format!("{:.2}", res.cl),
-            format!("{:.2}", res.cs),
-            format!("{:.2} m²", res.cda),
-            format!("{:.2} m²", res.cla),
-            format!("{:.2} m²", res.csa),
-            format!("{:.2} Nm", res.mr),
-            format!("{:.2} Nm", res.my),
-            format!("{:.2} Nm", res.mp),
-            format!("{:.2} W/K", res.heat_transfer),
-            format!("{:.2} W/m²K", res.heat_transfer_coefficient),
-        ]);
+        ];
+        row.extend(processing.row(sim.params.fluid_speed.0, res));
+        table.add_row(row);
     }
 
     for col in table.column_iter_mut().skip(2) {
@@ -229,4 +219,10 @@ fn print_results_human(project: &ProjectV7, items: &[SimulationV7]) {
     }
 
     println!("{table}");
+
+    if let Some(speed) = processing.at_speed {
+        println!(
+            "Forces, moments and drag power rescaled to {speed} m/s assuming constant coefficients."
+        );
+    }
 }
@@ -4,7 +4,11 @@ pub mod create_project;
 pub mod create_simulation;
 pub mod delete_projects;
 pub mod delete_simulations;
+pub mod get_simulation;
 pub mod list_projects;
 pub mod list_reusable_models;
 pub mod list_simulations;
+pub mod plot;
+pub mod report;
 pub mod wait_for_simulations;
+pub mod watch;
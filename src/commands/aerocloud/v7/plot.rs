@@ -0,0 +1,172 @@
+use crate::{
+    aerocloud::{
+        Client,
+        metrics::{Metric, Series, bounds},
+        poll,
+        results::UnitSystem,
+        types::Id,
+    },
+    args::Args,
+};
+use color_eyre::eyre;
+use crossterm::style::Stylize;
+use ratatui::{
+    backend::IntoCrossterm,
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    symbols::{Marker, border},
+    text::Line,
+    widgets::{Axis, Block, Chart, Dataset, GraphType, Widget},
+};
+use std::io::{self, IsTerminal};
+
+const DEFAULT_WIDTH: u16 = 100;
+
+const PALETTE: &[Color] = &[
+    Color::Cyan,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Green,
+    Color::Red,
+    Color::Blue,
+    Color::LightCyan,
+    Color::LightYellow,
+    Color::LightMagenta,
+    Color::LightGreen,
+];
+
+pub async fn run(
+    args: &Args,
+    client: &Client,
+    ids: &[Id],
+    metric: Metric,
+    units: UnitSystem,
+    height: u16,
+) -> eyre::Result<()> {
+    let sims = poll::fetch_all(client, ids.iter().cloned())
+        .await?
+        .into_iter()
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    let mut series = vec![];
+
+    for sim in &sims {
+        if let Some(s) = Series::from_simulation(sim, metric, units) {
+            series.push(s);
+        } else {
+            tracing::warn!(
+                "simulation `{}` ({}) has no results yet, skipping",
+                sim.name,
+                sim.id
+            );
+        }
+    }
+
+    if series.is_empty() {
+        eyre::bail!("no results to plot");
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string(&series)?);
+    } else {
+        print_chart(&series, metric, units, height);
+    }
+
+    Ok(())
+}
+
+fn print_chart(
+    series: &[Series],
+    metric: Metric,
+    units: UnitSystem,
+    height: u16,
+) {
+    let width = crossterm::terminal::size().map_or(DEFAULT_WIDTH, |(w, _)| w);
+    let area = Rect::new(0, 0, width, height);
+
+    let legends: Vec<String> = series.iter().map(Series::legend).collect();
+
+    let datasets = series
+        .iter()
+        .zip(&legends)
+        .zip(PALETTE.iter().cycle())
+        .map(|((s, legend), color)| {
+            Dataset::default()
+                .name(legend.as_str())
+                .marker(Marker::Braille)
+                .graph_type(if s.points.len() > 1 {
+                    GraphType::Line
+                } else {
+                    GraphType::Scatter
+                })
+                .style(Style::new().fg(*color))
+                .data(&s.points)
+        })
+        .collect();
+
+    let (x_min, x_max) =
+        bounds(series.iter().flat_map(|s| &s.points).map(|p| p.0));
+    let (y_min, y_max) =
+        bounds(series.iter().flat_map(|s| &s.points).map(|p| p.1));
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::bordered()
+                .title(
+                    Line::from(format!(" {} vs yaw angle ", metric.label()))
+                        .centered(),
+                )
+                .border_set(border::PLAIN),
+        )
+        .x_axis(
+            Axis::default()
+                .title("Yaw angle [°]")
+                .bounds([x_min, x_max])
+                .labels(labels(x_min, x_max, 1)),
+        )
+        .y_axis(
+            Axis::default()
+                .title(metric.title(units))
+                .bounds([y_min, y_max])
+                .labels(labels(y_min, y_max, 3)),
+        )
+        .hidden_legend_constraints((
+            Constraint::Ratio(1, 1),
+            Constraint::Ratio(1, 1),
+        ));
+
+    let mut buf = Buffer::empty(area);
+    chart.render(area, &mut buf);
+
+    print_buffer(&buf, io::stdout().is_terminal());
+}
+
+fn labels(min: f64, max: f64, precision: usize) -> Vec<String> {
+    [min, f64::midpoint(min, max), max]
+        .iter()
+        .map(|v| format!("{v:.precision$}"))
+        .collect()
+}
+
+fn print_buffer(buf: &Buffer, colored: bool) {
+    let area = buf.area;
+
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+
+        for x in area.left()..area.right() {
+            let cell = &buf[(x, y)];
+
+            if colored && cell.fg != Color::Reset {
+                line.push_str(
+                    &cell.symbol().with(cell.fg.into_crossterm()).to_string(),
+                );
+            } else {
+                line.push_str(cell.symbol());
+            }
+        }
+
+        println!("{}", line.trim_end());
+    }
+}
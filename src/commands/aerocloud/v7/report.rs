@@ -0,0 +1,385 @@
+use crate::{
+    aerocloud::{
+        Client,
+        cache::Cache,
+        fmt,
+        metrics::{Metric, Series},
+        results::{Processing, UnitSystem},
+        types::{Id, ProjectV7, SimulationStatus, SimulationV7},
+    },
+    args::Args,
+};
+use chrono::Local;
+use clap::ValueEnum;
+use color_eyre::eyre::{self, WrapErr};
+use itertools::Itertools;
+use minijinja::Environment;
+use std::{fmt::Write as _, path::Path};
+use tokio::fs;
+
+mod svg;
+
+const HTML_TEMPLATE: &str = include_str!("../../../templates/report.html.j2");
+const MARKDOWN_TEMPLATE: &str = include_str!("../../../templates/report.md.j2");
+
+const PARAMS_HEADER: &[&str] = &[
+    "Name",
+    "Revision",
+    "Status",
+    "Quality",
+    "Yaw angle(s)",
+    "Fluid & Speed",
+    "Boundary",
+    "Boundary layer treatment",
+    "Created at",
+];
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    Markdown,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Context<'a> {
+    project: &'a ProjectV7,
+    generated_at: String,
+    params_header: &'static [&'static str],
+    results_header: Vec<String>,
+    at_speed: Option<f64>,
+    simulations: Vec<SimulationEntry<'a>>,
+    charts: Vec<Chart>,
+    deltas: Vec<RevisionDelta>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct SimulationEntry<'a> {
+    sim: &'a SimulationV7,
+    params: Vec<String>,
+    results: Vec<Vec<String>>,
+    attachments: Vec<(&'static str, String)>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Chart {
+    metric: &'static str,
+    slug: String,
+    svg: String,
+    /// Where Markdown reports load the chart from, as they can't inline it.
+    src: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct RevisionDelta {
+    name: String,
+    from: String,
+    to: String,
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    args: &Args,
+    client: &Client,
+    cache: &Cache,
+    project_id: &Id,
+    format: ReportFormat,
+    metrics: &[Metric],
+    processing: &Processing,
+    output: Option<&Path>,
+) -> eyre::Result<()> {
+    let project = cache
+        .project(project_id, async {
+            Ok(client.projects_v7_get(project_id).await?.into_inner())
+        })
+        .await?;
+
+    let mut sims = super::list_simulations::fetch_all(
+        client,
+        cache,
+        project_id,
+        &super::list_simulations::Query::default(),
+    )
+    .await?;
+
+    sims.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| a.created_at.cmp(&b.created_at))
+    });
+
+    let mut charts = charts(&sims, metrics, processing.units);
+
+    if let ReportFormat::Markdown = format {
+        for chart in &mut charts {
+            chart.src = match output {
+                Some(path) => write_chart(path, chart).await?,
+                None => data_uri(&chart.svg),
+            };
+        }
+    }
+
+    let context = Context {
+        project: &project,
+        generated_at: Local::now().to_string(),
+        params_header: PARAMS_HEADER,
+        results_header: std::iter::once("Yaw angle".to_owned())
+            .chain(processing.header())
+            .collect(),
+        at_speed: processing.at_speed,
+        simulations: sims
+            .iter()
+            .map(|sim| simulation_entry(sim, processing))
+            .collect(),
+        charts,
+        deltas: revision_deltas(&sims, metrics, processing.units),
+    };
+
+    let rendered = render(format, &context)?;
+
+    if let Some(path) = output {
+        fs::write(path, rendered).await.wrap_err_with(|| {
+            format!("writing report to `{}`", path.display())
+        })?;
+
+        if args.json {
+            println!(
+                "{}",
+                serde_json::to_string(&serde_json::json!({ "path": path }))?
+            );
+        } else {
+            println!("Written report to `{}`", path.display());
+        }
+    } else {
+        print!("{rendered}");
+    }
+
+    Ok(())
+}
+
+fn render(format: ReportFormat, context: &Context<'_>) -> eyre::Result<String> {
+    let mut env = Environment::new();
+
+    // NOTE: the `.html` suffix enables auto-escaping.
+    let (name, source) = match format {
+        ReportFormat::Html => ("report.html", HTML_TEMPLATE),
+        ReportFormat::Markdown => ("report.md", MARKDOWN_TEMPLATE),
+    };
+
+    env.add_template(name, source)?;
+    env.add_filter("cell", |s: String| s.replace('|', "\\|").replace('\n', " "));
+
+    env.get_template(name)?
+        .render(context)
+        .wrap_err("rendering report")
+}
+
+fn simulation_entry<'a>(
+    sim: &'a SimulationV7,
+    processing: &Processing,
+) -> SimulationEntry<'a> {
+    let params = vec![
+        sim.name.clone(),
+        sim.revision.clone().unwrap_or_default(),
+        fmt::human_simulation_status(sim.status).into(),
+        sim.params.quality.to_string(),
+        sim.params
+            .yaw_angles
+            .iter()
+            .map(|v| format!("{v}°"))
+            .join(", "),
+        format!("{}, {} m/s", sim.params.fluid, sim.params.fluid_speed),
+        fmt::human_boundary(&sim.params),
+        sim.params
+            .boundary_layer_treatment
+            .map(fmt::human_boundary_layer_treatment)
+            .unwrap_or_default()
+            .into(),
+        sim.created_at.with_timezone(&Local).to_string(),
+    ];
+
+    let Some(results) = &sim.results else {
+        return SimulationEntry {
+            sim,
+            params,
+            results: vec![],
+            attachments: vec![],
+        };
+    };
+
+    let rows = results
+        .yaw_angles
+        .iter()
+        .map(|res| {
+            let mut row = vec![format!("{}°", res.yaw_angle)];
+            row.extend(processing.row(sim.params.fluid_speed.0, res));
+            row
+        })
+        .collect();
+
+    let attachments = &results.attachments;
+
+    let attachments = [
+        ("Report", &attachments.report_url),
+        ("Spreadsheet", &attachments.spreadsheet_url),
+        ("Raw data", &attachments.raw_data_url),
+        ("Slice images", &attachments.slice_images_url),
+    ]
+    .into_iter()
+    .filter_map(|(label, url)| Some((label, url.as_ref()?.0.clone())))
+    .collect();
+
+    SimulationEntry {
+        sim,
+        params,
+        results: rows,
+        attachments,
+    }
+}
+
+fn charts(
+    sims: &[SimulationV7],
+    metrics: &[Metric],
+    units: UnitSystem,
+) -> Vec<Chart> {
+    metrics
+        .iter()
+        .filter_map(|metric| {
+            let series: Vec<Series> = sims
+                .iter()
+                .filter(|sim| sim.status == SimulationStatus::Success)
+                .filter_map(|sim| Series::from_simulation(sim, *metric, units))
+                .filter(|series| !series.points.is_empty())
+                .collect();
+
+            if series.is_empty() {
+                return None;
+            }
+
+            Some(Chart {
+                metric: metric.label(),
+                slug: metric
+                    .to_possible_value()
+                    .map(|value| value.get_name().to_owned())
+                    .unwrap_or_default(),
+                svg: svg::chart(&series, *metric, units),
+                src: String::new(),
+            })
+        })
+        .collect()
+}
+
+/// Writes `chart` next to the report at `path`, returning its path relative to it.
+async fn write_chart(path: &Path, chart: &Chart) -> eyre::Result<String> {
+    let stem = path
+        .file_stem()
+        .map_or_else(|| "report".into(), |stem| stem.to_string_lossy());
+    let filename = format!("{stem}-{}.svg", chart.slug);
+    let chart_path = path.with_file_name(&filename);
+
+    fs::write(&chart_path, &chart.svg).await.wrap_err_with(|| {
+        format!("writing chart to `{}`", chart_path.display())
+    })?;
+
+    Ok(filename)
+}
+
+/// Inlines `svg` as a data URI, for reports printed rather than written.
+fn data_uri(svg: &str) -> String {
+    let mut uri = "data:image/svg+xml,".to_owned();
+
+    for byte in svg.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            uri.push(char::from(byte));
+        } else {
+            let _ = write!(uri, "%{byte:02X}");
+        }
+    }
+
+    uri
+}
+
+/// Compares results of consecutive revisions of simulations sharing the same name,
+/// at the yaw angles they have in common.
+fn revision_deltas(
+    sims: &[SimulationV7],
+    metrics: &[Metric],
+    units: UnitSystem,
+) -> Vec<RevisionDelta> {
+    let mut deltas = vec![];
+
+    let with_results = sims
+        .iter()
+        .filter(|sim| sim.results.is_some())
+        .chunk_by(|sim| &sim.name);
+
+    for (name, group) in &with_results {
+        let group: Vec<&SimulationV7> = group.collect();
+
+        for (prev, next) in group.iter().tuple_windows() {
+            let (Some(prev_results), Some(next_results)) =
+                (&prev.results, &next.results)
+            else {
+                continue;
+            };
+
+            let rows: Vec<Vec<String>> = next_results
+                .yaw_angles
+                .iter()
+                .filter_map(|next_res| {
+                    let prev_res =
+                        prev_results.yaw_angles.iter().find(|res| {
+                            (res.yaw_angle.0 - next_res.yaw_angle.0).abs()
+                                < f64::EPSILON
+                        })?;
+
+                    let mut row = vec![format!("{}°", next_res.yaw_angle)];
+
+                    row.extend(metrics.iter().map(|metric| {
+                        fmt_delta(
+                            metric.value_in(prev_res, units),
+                            metric.value_in(next_res, units),
+                        )
+                    }));
+
+                    Some(row)
+                })
+                .collect();
+
+            if rows.is_empty() {
+                continue;
+            }
+
+            deltas.push(RevisionDelta {
+                name: name.clone(),
+                from: revision_label(prev),
+                to: revision_label(next),
+                header: std::iter::once("Yaw angle".to_owned())
+                    .chain(metrics.iter().map(|m| format!("Δ{}", m.title(units))))
+                    .collect(),
+                rows,
+            });
+        }
+    }
+
+    deltas
+}
+
+fn revision_label(sim: &SimulationV7) -> String {
+    match &sim.revision {
+        Some(revision) => revision.clone(),
+        None => format!("created {}", sim.created_at.with_timezone(&Local)),
+    }
+}
+
+fn fmt_delta(prev: f64, next: f64) -> String {
+    let delta = next - prev;
+
+    if prev == 0.0 {
+        format!("{delta:+.4}")
+    } else {
+        format!("{delta:+.4} ({:+.1}%)", delta / prev.abs() * 100.0)
+    }
+}
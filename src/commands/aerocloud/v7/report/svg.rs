@@ -0,0 +1,131 @@
+use crate::aerocloud::{
+    metrics::{Metric, Series, bounds},
+    results::UnitSystem,
+};
+use std::fmt::Write as _;
+
+const WIDTH: f64 = 720.0;
+const HEIGHT: f64 = 380.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 190.0;
+const MARGIN_TOP: f64 = 30.0;
+const MARGIN_BOTTOM: f64 = 50.0;
+const TICKS: usize = 5;
+
+const PALETTE: &[&str] = &[
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2",
+    "#7f7f7f", "#bcbd22", "#17becf",
+];
+
+/// Renders a line chart of `metric` against yaw angle, one line per series.
+pub fn chart(series: &[Series], metric: Metric, units: UnitSystem) -> String {
+    let points = series.iter().flat_map(|s| &s.points);
+    let (x_min, x_max) = bounds(points.clone().map(|p| p.0));
+    let (y_min, y_max) = bounds(points.map(|p| p.1));
+
+    let plot_w = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_h = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+
+    let x = |v: f64| MARGIN_LEFT + (v - x_min) / (x_max - x_min) * plot_w;
+    let y = |v: f64| MARGIN_TOP + plot_h - (v - y_min) / (y_max - y_min) * plot_h;
+
+    let mut svg = String::new();
+
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {WIDTH} {HEIGHT}" width="{WIDTH}" height="{HEIGHT}" font-family="sans-serif" font-size="12">"#
+    );
+
+    #[allow(clippy::cast_precision_loss)]
+    for i in 0..=TICKS {
+        let t = i as f64 / TICKS as f64;
+
+        let xv = x_min + t * (x_max - x_min);
+        let yv = y_min + t * (y_max - y_min);
+
+        let _ = write!(
+            svg,
+            r##"<line x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="{bottom}" stroke="#e0e0e0"/><text x="{x:.1}" y="{label_y}" text-anchor="middle">{xv:.1}</text>"##,
+            x = x(xv),
+            top = MARGIN_TOP,
+            bottom = MARGIN_TOP + plot_h,
+            label_y = MARGIN_TOP + plot_h + 18.0,
+        );
+        let _ = write!(
+            svg,
+            r##"<line x1="{left}" y1="{y:.1}" x2="{right}" y2="{y:.1}" stroke="#e0e0e0"/><text x="{label_x}" y="{label_y:.1}" text-anchor="end">{yv:.3}</text>"##,
+            y = y(yv),
+            left = MARGIN_LEFT,
+            right = MARGIN_LEFT + plot_w,
+            label_x = MARGIN_LEFT - 6.0,
+            label_y = y(yv) + 4.0,
+        );
+    }
+
+    let _ = write!(
+        svg,
+        r##"<rect x="{MARGIN_LEFT}" y="{MARGIN_TOP}" width="{plot_w}" height="{plot_h}" fill="none" stroke="#555"/>"##
+    );
+
+    let _ = write!(
+        svg,
+        r#"<text x="{:.1}" y="{}" text-anchor="middle">Yaw angle [°]</text>"#,
+        MARGIN_LEFT + plot_w / 2.0,
+        HEIGHT - 10.0,
+    );
+
+    let _ = write!(
+        svg,
+        r#"<text transform="translate(16 {:.1}) rotate(-90)" text-anchor="middle">{}</text>"#,
+        MARGIN_TOP + plot_h / 2.0,
+        escape(&metric.title(units)),
+    );
+
+    for (idx, (s, color)) in series.iter().zip(PALETTE.iter().cycle()).enumerate()
+    {
+        let polyline = s
+            .points
+            .iter()
+            .map(|(px, py)| format!("{:.1},{:.1}", x(*px), y(*py)))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let _ = write!(
+            svg,
+            r#"<polyline points="{polyline}" fill="none" stroke="{color}" stroke-width="2"/>"#
+        );
+
+        for (px, py) in &s.points {
+            let _ = write!(
+                svg,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{color}"/>"#,
+                x(*px),
+                y(*py),
+            );
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let legend_y = MARGIN_TOP + 10.0 + idx as f64 * 18.0;
+        let legend_x = MARGIN_LEFT + plot_w + 14.0;
+
+        let _ = write!(
+            svg,
+            r#"<line x1="{legend_x}" y1="{legend_y:.1}" x2="{:.1}" y2="{legend_y:.1}" stroke="{color}" stroke-width="2"/><text x="{:.1}" y="{:.1}">{}</text>"#,
+            legend_x + 18.0,
+            legend_x + 24.0,
+            legend_y + 4.0,
+            escape(&s.legend()),
+        );
+    }
+
+    svg.push_str("</svg>");
+
+    svg
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
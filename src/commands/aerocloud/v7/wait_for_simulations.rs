@@ -1,71 +1,328 @@
 use crate::{
     aerocloud::{
         Client,
-        types::{Id, SimulationStatus},
+        poll::{self, Discovery},
+        types::{Id, SimulationStatus, SimulationV7, SimulationsV7ListStatus},
     },
     args::Args,
-    fmt::link,
+    fmt::{NOT_AVAILABLE, link},
+    notify::{Notification, Notifiers},
+    utils::{ExitCode, new_dynamic_table},
 };
 use color_eyre::eyre::{self, bail};
 use std::time::Duration;
+use tokio::time::Instant;
 use tracing::{debug, info, warn};
-use uuid::Uuid;
 
-pub async fn run(args: &Args, client: &Client, ids: &[Id]) -> eyre::Result<()> {
-    if ids.is_empty() {
+/// How waiting for a simulation ended, ordered by severity. The process exits with
+/// the code of the most severe outcome.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Outcome {
+    Succeeded,
+    Expired,
+    Draft,
+    TimedOut,
+}
+
+impl Outcome {
+    fn from_status(status: SimulationStatus) -> Option<Self> {
+        match status {
+            SimulationStatus::Success => Some(Self::Succeeded),
+            SimulationStatus::Expired => Some(Self::Expired),
+            SimulationStatus::Draft => Some(Self::Draft),
+            SimulationStatus::Progress => None,
+        }
+    }
+
+    fn exit_code(self) -> i32 {
+        match self {
+            Self::Succeeded => 0,
+            Self::Expired => 3,
+            Self::Draft => 4,
+            Self::TimedOut => 5,
+        }
+    }
+
+    fn human(self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Expired => "expired",
+            Self::Draft => "not submitted",
+            Self::TimedOut => "timed out",
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Succeeded => "has completed",
+            Self::Expired => "has expired",
+            Self::Draft => "has not been submitted",
+            Self::TimedOut => "has not completed in time",
+        }
+    }
+}
+
+struct Waited {
+    id: Id,
+    sim: Option<SimulationV7>,
+    outcome: Option<Outcome>,
+}
+
+pub async fn run(
+    args: &Args,
+    client: &Client,
+    notifiers: &Notifiers,
+    ids: &[Id],
+    discovery: Option<Discovery>,
+    interval: Duration,
+    timeout: Option<Duration>,
+) -> eyre::Result<()> {
+    if ids.is_empty() && discovery.is_none() {
         bail!("No ids were specified!");
     }
 
-    let mut ids_to_wait_for: Vec<Uuid> =
-        ids.iter().map(|id| **id).collect::<Vec<_>>();
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    let mut waited: Vec<Waited> = ids
+        .iter()
+        .map(|id| Waited {
+            id: id.clone(),
+            sim: None,
+            outcome: None,
+        })
+        .collect();
+
+    let mut first_round = true;
 
     loop {
-        info!(
-            "waiting for {} simulation(s) to complete...",
-            ids_to_wait_for.len()
+        let round = poll_round(
+            args,
+            client,
+            notifiers,
+            discovery.as_ref(),
+            &mut waited,
+            &mut first_round,
         );
 
-        let mut found_ids = vec![];
-
-        for id in &ids_to_wait_for {
-            debug!("polling sim `{id}`...");
-
-            match client.simulations_v7_get(&Id(*id)).await {
-                Ok(res) => {
-                    let sim = res.into_inner();
-
-                    if let SimulationStatus::Success | SimulationStatus::Expired =
-                        sim.status
-                    {
-                        found_ids.push(*id);
-
-                        if args.json {
-                            println!("{}", serde_json::to_string(&sim)?);
-                        } else {
-                            println!(
-                                "Simulation `{}` has completed. {}",
-                                sim.id,
-                                link(&sim.browser_url)
-                            );
-                        }
-                    } else {
-                        debug!("sim `{id}` still in progress...");
-                    }
+        // NOTE: a round can take long with many sims or a slow API, so the
+        // deadline applies to it as well as to the sleep between rounds.
+        let polled = match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, round).await.ok(),
+            None => Some(round.await),
+        };
+
+        let Some(res) = polled else {
+            time_out(args, notifiers, &mut waited).await?;
+            break;
+        };
+
+        res?;
+
+        if waited.iter().all(|w| w.outcome.is_some()) {
+            break;
+        }
+
+        let sleep_for = match deadline {
+            Some(deadline) => {
+                let remaining =
+                    deadline.saturating_duration_since(Instant::now());
+
+                if remaining.is_zero() {
+                    time_out(args, notifiers, &mut waited).await?;
+                    break;
                 }
-                Err(err) => {
-                    warn!("failed to poll sim `{id}`: {err}. will retry later.");
+
+                interval.min(remaining)
+            }
+            None => interval,
+        };
+
+        tokio::time::sleep(sleep_for).await;
+    }
+
+    if args.template.is_none() && !args.json {
+        print_summary_human(&waited);
+    }
+
+    let code = waited
+        .iter()
+        .filter_map(|w| w.outcome)
+        .max()
+        .map_or(0, Outcome::exit_code);
+
+    if code != 0 {
+        return Err(ExitCode(code).into());
+    }
+
+    Ok(())
+}
+
+/// Discovers new simulations, if asked to, then polls those without an outcome.
+async fn poll_round(
+    args: &Args,
+    client: &Client,
+    notifiers: &Notifiers,
+    discovery: Option<&Discovery>,
+    waited: &mut Vec<Waited>,
+    first_round: &mut bool,
+) -> eyre::Result<()> {
+    if let Some(discovery) = discovery {
+        match poll::discover(
+            client,
+            discovery,
+            Some(SimulationsV7ListStatus::InProgress),
+        )
+        .await
+        {
+            Ok(discovered) => {
+                for SimulationV7 { id, .. } in discovered {
+                    if waited.iter().all(|w| w.id.0 != id.0) {
+                        info!("discovered sim `{id}`");
+
+                        waited.push(Waited {
+                            id,
+                            sim: None,
+                            outcome: None,
+                        });
+                    }
                 }
             }
+            Err(err) if !*first_round => {
+                warn!("{err:#}. will retry later.");
+            }
+            Err(err) => return Err(err),
         }
 
-        ids_to_wait_for.retain(|id| !found_ids.contains(id));
+        *first_round = false;
+    }
 
-        if ids_to_wait_for.is_empty() {
-            break;
+    if waited.iter().all(|w| w.outcome.is_some()) {
+        return Ok(());
+    }
+
+    poll_pending(args, client, notifiers, waited).await
+}
+
+/// Marks all simulations without an outcome yet as timed out.
+async fn time_out(
+    args: &Args,
+    notifiers: &Notifiers,
+    waited: &mut [Waited],
+) -> eyre::Result<()> {
+    for w in waited.iter_mut().filter(|w| w.outcome.is_none()) {
+        w.outcome = Some(Outcome::TimedOut);
+
+        if let Some(sim) = &w.sim {
+            finished(args, notifiers, sim, Outcome::TimedOut).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Polls all simulations without an outcome yet, recording those which reached one.
+async fn poll_pending(
+    args: &Args,
+    client: &Client,
+    notifiers: &Notifiers,
+    waited: &mut [Waited],
+) -> eyre::Result<()> {
+    let pending: Vec<usize> = waited
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| w.outcome.is_none())
+        .map(|(idx, _)| idx)
+        .collect();
+
+    info!("waiting for {} simulation(s) to complete...", pending.len());
+
+    let polled = poll::fetch_all(
+        client,
+        pending.iter().map(|idx| waited[*idx].id.clone()),
+    )
+    .await?;
+
+    for (idx, res) in pending.into_iter().zip(polled) {
+        let sim = match res {
+            Ok(sim) => sim,
+            Err(err) => {
+                warn!("{err:#}. will retry later.");
+                continue;
+            }
+        };
+
+        let outcome = Outcome::from_status(sim.status);
+
+        if outcome.is_none() {
+            debug!("sim `{}` still in progress...", sim.id);
         }
 
-        tokio::time::sleep(Duration::from_mins(1)).await;
+        // NOTE: recorded before reporting, which may be cut short by the
+        // deadline, so that the sim isn't reported again as timed out.
+        waited[idx].outcome = outcome;
+        let sim = waited[idx].sim.insert(sim);
+
+        if let Some(outcome) = outcome {
+            finished(args, notifiers, sim, outcome).await?;
+        }
     }
 
     Ok(())
 }
+
+async fn finished(
+    args: &Args,
+    notifiers: &Notifiers,
+    sim: &SimulationV7,
+    outcome: Outcome,
+) -> eyre::Result<()> {
+    if let Some(template) = &args.template {
+        template.print(sim)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string(sim)?);
+    } else {
+        println!(
+            "Simulation `{}` {}. {}",
+            sim.id,
+            outcome.describe(),
+            link(&sim.browser_url)
+        );
+    }
+
+    notifiers
+        .send(&Notification {
+            title: format!("Simulation {}", outcome.human()),
+            message: format!(
+                "`{}` {}: {}",
+                sim.name,
+                outcome.describe(),
+                sim.browser_url
+            ),
+            simulations: vec![sim.clone()],
+        })
+        .await;
+
+    Ok(())
+}
+
+fn print_summary_human(waited: &[Waited]) {
+    let mut table = new_dynamic_table();
+    table.set_header(vec!["Id", "Name", "Outcome", ""]);
+
+    for w in waited {
+        table.add_row(vec![
+            w.id.to_string(),
+            w.sim
+                .as_ref()
+                .map_or(NOT_AVAILABLE, |sim| sim.name.as_str())
+                .to_owned(),
+            w.outcome.map_or(NOT_AVAILABLE, Outcome::human).to_owned(),
+            w.sim
+                .as_ref()
+                .map(|sim| link(&sim.browser_url))
+                .unwrap_or_default(),
+        ]);
+    }
+
+    println!("\n{table}");
+}
@@ -0,0 +1,128 @@
+use crate::{
+    aerocloud::{
+        Client,
+        poll::{self, Discovery},
+        types::{Id, SimulationStatus, SimulationV7, Url},
+    },
+    args::Args,
+};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{self, bail};
+use std::{collections::HashMap, time::Duration};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Emitted whenever a simulation is first seen or changes status.
+#[derive(Debug, serde::Serialize)]
+pub struct Event<'a> {
+    pub at: DateTime<Utc>,
+    pub id: &'a Id,
+    pub project_id: &'a Id,
+    pub name: &'a str,
+    pub revision: Option<&'a str>,
+    pub from: Option<SimulationStatus>,
+    pub to: SimulationStatus,
+    pub browser_url: &'a Url,
+    pub results: Option<Vec<ResultSummary>>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ResultSummary {
+    pub yaw_angle: f64,
+    pub cd: f64,
+    pub cl: f64,
+    pub cs: f64,
+    pub cda: f64,
+    pub fd: f64,
+    pub fl: f64,
+    pub fs: f64,
+}
+
+impl<'a> Event<'a> {
+    pub fn new(sim: &'a SimulationV7, from: Option<SimulationStatus>) -> Self {
+        Self {
+            at: Utc::now(),
+            id: &sim.id,
+            project_id: &sim.project_id,
+            name: &sim.name,
+            revision: sim.revision.as_deref(),
+            from,
+            to: sim.status,
+            browser_url: &sim.browser_url,
+            results: sim.results.as_ref().map(|results| {
+                results
+                    .yaw_angles
+                    .iter()
+                    .map(|res| ResultSummary {
+                        yaw_angle: res.yaw_angle.0,
+                        cd: res.cd,
+                        cl: res.cl,
+                        cs: res.cs,
+                        cda: res.cda,
+                        fd: res.fd,
+                        fl: res.fl,
+                        fs: res.fs,
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+pub async fn run(
+    args: &Args,
+    client: &Client,
+    ids: &[Id],
+    discovery: Option<Discovery>,
+    interval: Duration,
+) -> eyre::Result<()> {
+    if ids.is_empty() && discovery.is_none() {
+        bail!("No ids were specified!");
+    }
+
+    let mut last_status: HashMap<Uuid, SimulationStatus> = HashMap::new();
+    let mut first_round = true;
+
+    loop {
+        let mut sims = vec![];
+
+        for res in poll::fetch_all(client, ids.iter().cloned()).await? {
+            match res {
+                Ok(sim) => sims.push(sim),
+                Err(err) => warn!("{err:#}. will retry later."),
+            }
+        }
+
+        if let Some(discovery) = &discovery {
+            match poll::discover(client, discovery, None).await {
+                Ok(discovered) => sims.extend(discovered),
+                Err(err) if !first_round => {
+                    warn!("{err:#}. will retry later.");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        for sim in &sims {
+            let from = last_status.insert(sim.id.0, sim.status);
+
+            if from != Some(sim.status) {
+                print_event(args, &Event::new(sim, from))?;
+            }
+        }
+
+        first_round = false;
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+fn print_event(args: &Args, event: &Event<'_>) -> eyre::Result<()> {
+    if let Some(template) = &args.template {
+        template.print(event)?;
+    } else {
+        println!("{}", serde_json::to_string(event)?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use crate::{aerocloud::cache::Cache, args::Args};
+use color_eyre::eyre;
+
+pub async fn run(args: &Args, cache: &Cache) -> eyre::Result<()> {
+    let removed = cache.clear().await?;
+
+    if args.json {
+        println!("{}", serde_json::json!({ "removed": removed }));
+    } else {
+        println!(
+            "Removed {removed} cached listing(s) from `{}`",
+            cache.dir().display()
+        );
+    }
+
+    Ok(())
+}
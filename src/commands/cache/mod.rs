@@ -0,0 +1,17 @@
+use crate::{
+    aerocloud::cache::Cache,
+    args::{Args, CacheScope},
+};
+use color_eyre::eyre;
+
+pub mod clear;
+pub mod stats;
+
+pub async fn run(args: &Args, subcommand: &CacheScope) -> eyre::Result<()> {
+    let cache = Cache::new(args);
+
+    match subcommand {
+        CacheScope::Clear => self::clear::run(args, &cache).await,
+        CacheScope::Stats => self::stats::run(args, &cache).await,
+    }
+}
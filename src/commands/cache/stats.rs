@@ -0,0 +1,63 @@
+use crate::{
+    aerocloud::cache::{Cache, EntryStats},
+    args::Args,
+    utils::new_dynamic_table,
+};
+use chrono::Local;
+use color_eyre::eyre;
+
+pub async fn run(args: &Args, cache: &Cache) -> eyre::Result<()> {
+    let stats = cache.stats().await?;
+
+    if let Some(template) = &args.template {
+        template.print_all(&stats)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string(&stats)?);
+    } else {
+        print_human(cache, &stats);
+    }
+
+    Ok(())
+}
+
+fn print_human(cache: &Cache, stats: &[EntryStats]) {
+    println!("Cache: `{}`", cache.dir().display());
+
+    if stats.is_empty() {
+        println!("\n<empty>");
+        return;
+    }
+
+    let mut table = new_dynamic_table();
+    table.set_header(vec!["Listing", "Items", "Size", "Fetched at"]);
+
+    for entry in stats {
+        table.add_row(vec![
+            entry.name.clone(),
+            entry.items.to_string(),
+            human_bytes(entry.bytes),
+            entry.fetched_at.with_timezone(&Local).to_string(),
+        ]);
+    }
+
+    println!("{table}");
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
@@ -6,6 +6,7 @@ use color_eyre::eyre;
 
 pub mod set_hostname;
 pub mod show;
+pub mod trust_hostname;
 pub mod unset_hostname;
 
 pub async fn run(
@@ -20,6 +21,9 @@ pub async fn run(
         ConfigScope::UnsetHostname => {
             self::unset_hostname::run(args, config).await
         }
+        ConfigScope::TrustHostname { hostname } => {
+            self::trust_hostname::run(args, config, hostname).await
+        }
         ConfigScope::Show => self::show::run(args, &config),
     }
 }
@@ -7,7 +7,7 @@ pub async fn run(
     mut config: Config,
     hostname: &Url,
 ) -> eyre::Result<()> {
-    config.hostname = Some(hostname.to_owned());
+    config.active_profile_mut().hostname = Some(hostname.to_owned());
 
     config.write(&args.config_path).await
 }
@@ -16,6 +16,9 @@ fn print_json(config: &Config) -> eyre::Result<()> {
         "{}",
         serde_json::to_string(&serde_json::json!({
             "hostname": config.hostname().to_string(),
+            "profile": config.profile,
+            "project_file": config.project.path,
+            "project_id": config.project.project_id,
         }))?
     );
 
@@ -28,5 +31,17 @@ fn print_human(config: &Config) {
         .set_header(vec!["Key", "Value"])
         .add_row(vec!["Hostname", config.hostname().as_ref()]);
 
+    if let Some(profile) = &config.profile {
+        table.add_row(vec!["Profile", profile]);
+    }
+
+    if let Some(path) = &config.project.path {
+        table.add_row(vec!["Project file".into(), path.display().to_string()]);
+    }
+
+    if let Some(project_id) = &config.project.project_id {
+        table.add_row(vec!["Project id".into(), project_id.to_string()]);
+    }
+
     println!("{table}");
 }
@@ -0,0 +1,19 @@
+use crate::{args::Args, config::Config};
+use color_eyre::eyre;
+use reqwest::Url;
+
+pub async fn run(
+    args: &Args,
+    mut config: Config,
+    hostname: &Url,
+) -> eyre::Result<()> {
+    if !config
+        .trusted_hostnames
+        .iter()
+        .any(|h| h == hostname.as_str())
+    {
+        config.trusted_hostnames.push(hostname.to_string());
+    }
+
+    config.write(&args.config_path).await
+}
@@ -2,7 +2,7 @@ use crate::{args::Args, config::Config};
 use color_eyre::eyre;
 
 pub async fn run(args: &Args, mut config: Config) -> eyre::Result<()> {
-    config.hostname = None;
+    config.active_profile_mut().hostname = None;
 
     config.write(&args.config_path).await
 }
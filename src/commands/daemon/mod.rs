@@ -0,0 +1,42 @@
+use crate::{
+    args::{Args, DaemonScope},
+    config::Config,
+};
+use color_eyre::eyre;
+use std::time::Duration;
+
+pub mod run;
+pub mod status;
+pub mod track;
+pub mod wait;
+
+pub async fn run(
+    args: &Args,
+    config: Config,
+    subcommand: &DaemonScope,
+) -> eyre::Result<()> {
+    match subcommand {
+        DaemonScope::Run {
+            store_path,
+            download_dir,
+            min_interval_secs,
+            max_interval_secs,
+        } => {
+            self::run::run(
+                args,
+                &config,
+                store_path,
+                download_dir.as_deref(),
+                Duration::from_secs(*min_interval_secs),
+                Duration::from_secs(*max_interval_secs),
+            )
+            .await
+        }
+        DaemonScope::Track { ids } => self::track::run(args, ids).await,
+        DaemonScope::Status { ids } => self::status::run(args, ids).await,
+        DaemonScope::Wait { ids, timeout_secs } => {
+            self::wait::run(args, ids, timeout_secs.map(Duration::from_secs))
+                .await
+        }
+    }
+}
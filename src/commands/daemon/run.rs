@@ -0,0 +1,475 @@
+use crate::{
+    aerocloud::{
+        Client, poll,
+        types::{Id, SimulationV7},
+    },
+    args::Args,
+    config::Config,
+    daemon::{
+        self, Envelope, Request, Response,
+        store::{Store, Tracked},
+    },
+    http,
+    notify::{Notification, Notifiers},
+};
+use chrono::Utc;
+use color_eyre::eyre::{self, WrapErr};
+use futures_util::StreamExt;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    fs,
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::{Mutex, watch},
+};
+use tracing::{debug, info, warn};
+
+/// How often the store is checked for simulations due to be polled.
+const TICK: Duration = Duration::from_secs(2);
+
+struct Daemon {
+    /// Clients must send along with requests, see [`daemon::write_token`].
+    token: String,
+    store: Mutex<Store>,
+    /// Signalled whenever tracked simulations are updated.
+    updated: watch::Sender<()>,
+}
+
+struct Poller {
+    client: Client,
+    notifiers: Notifiers,
+    http_client: reqwest::Client,
+    download_dir: Option<PathBuf>,
+    min_interval: Duration,
+    max_interval: Duration,
+}
+
+pub async fn run(
+    args: &Args,
+    config: &Config,
+    store_path: &Path,
+    download_dir: Option<&Path>,
+    min_interval: Duration,
+    max_interval: Duration,
+) -> eyre::Result<()> {
+    let poller = Poller {
+        client: http::build_aerocloud_client_from_config(
+            config,
+            &args.http_timeout(),
+        )?,
+        notifiers: Notifiers::new(args, config)?,
+        http_client: http::build_http_client_without_token(
+            &http::UPLOAD_REQ_TIMEOUT,
+        )?,
+        download_dir: download_dir.map(ToOwned::to_owned),
+        min_interval,
+        max_interval: max_interval.max(min_interval),
+    };
+
+    let store = Store::load(store_path).await?;
+
+    let listener = TcpListener::bind(args.daemon_addr)
+        .await
+        .wrap_err_with(|| format!("listening on `{}`", args.daemon_addr))?;
+
+    // NOTE: written once listening, not to replace the token of a daemon
+    // already running.
+    let daemon = Arc::new(Daemon {
+        token: daemon::write_token().await?,
+        store: Mutex::new(store),
+        updated: watch::Sender::new(()),
+    });
+
+    if !args.json {
+        println!(
+            "Daemon listening on `{}`, tracking simulations in `{}`",
+            args.daemon_addr,
+            store_path.display()
+        );
+    }
+
+    tokio::select! {
+        res = serve(listener, daemon.clone()) => res,
+        res = poller.run(&daemon) => res,
+        res = tokio::signal::ctrl_c() => {
+            info!("shutting down");
+            Ok(res?)
+        }
+    }
+}
+
+async fn serve(listener: TcpListener, daemon: Arc<Daemon>) -> eyre::Result<()> {
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let daemon = daemon.clone();
+
+        debug!("accepted connection from `{peer}`");
+
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, &daemon).await {
+                warn!("connection from `{peer}` failed: {err:#}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    daemon: &Daemon,
+) -> eyre::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        let (response, authorized) = match serde_json::from_str::<Envelope>(&line)
+        {
+            Ok(Envelope { token, .. }) if token != daemon.token => (
+                Response::Error {
+                    message: "invalid token".into(),
+                },
+                false,
+            ),
+            Ok(Envelope { request, .. }) => (
+                daemon.handle_request(request).await.unwrap_or_else(|err| {
+                    Response::Error {
+                        message: format!("{err:#}"),
+                    }
+                }),
+                true,
+            ),
+            Err(err) => (
+                Response::Error {
+                    message: format!("invalid request: {err}"),
+                },
+                true,
+            ),
+        };
+
+        let mut buf = serde_json::to_vec(&response)?;
+        buf.push(b'\n');
+        write.write_all(&buf).await?;
+
+        if !authorized {
+            eyre::bail!("invalid token");
+        }
+    }
+
+    Ok(())
+}
+
+impl Daemon {
+    async fn handle_request(&self, request: Request) -> eyre::Result<Response> {
+        match request {
+            Request::Track { ids } => {
+                self.track(&ids).await?;
+
+                Ok(Response::Ok)
+            }
+            Request::Status { ids } => Ok(Response::Simulations {
+                simulations: self.store.lock().await.select(&ids),
+            }),
+            Request::Wait { ids } => {
+                self.track(&ids).await?;
+
+                let mut updated = self.updated.subscribe();
+
+                loop {
+                    let simulations = self.store.lock().await.select(&ids);
+
+                    if simulations.iter().all(Tracked::is_done) {
+                        return Ok(Response::Simulations { simulations });
+                    }
+
+                    updated.changed().await?;
+                }
+            }
+        }
+    }
+
+    async fn track(&self, ids: &[Id]) -> eyre::Result<()> {
+        let mut store = self.store.lock().await;
+
+        if store.track(ids) > 0 {
+            store.save().await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Poller {
+    async fn run(&self, daemon: &Daemon) -> eyre::Result<()> {
+        loop {
+            let now = Utc::now();
+
+            let due: Vec<Id> = daemon
+                .store
+                .lock()
+                .await
+                .simulations
+                .iter()
+                .filter(|tracked| {
+                    !tracked.is_done() && tracked.next_poll_at <= now
+                })
+                .map(|tracked| tracked.id.clone())
+                .collect();
+
+            if !due.is_empty() {
+                for tracked in self.poll(daemon, due).await? {
+                    self.finished(&tracked).await;
+                }
+            }
+
+            if self.download_dir.is_some() {
+                let now = Utc::now();
+
+                let downloads: Vec<SimulationV7> = daemon
+                    .store
+                    .lock()
+                    .await
+                    .simulations
+                    .iter()
+                    .filter(|tracked| {
+                        tracked.needs_download() && tracked.next_poll_at <= now
+                    })
+                    .filter_map(|tracked| tracked.simulation.clone())
+                    .collect();
+
+                for sim in downloads {
+                    self.download(daemon, &sim).await?;
+                }
+            }
+
+            tokio::time::sleep(TICK).await;
+        }
+    }
+
+    /// Polls `due` simulations, updating the store and backing off those which did
+    /// not change. Returns the simulations which just reached a final status.
+    async fn poll(
+        &self,
+        daemon: &Daemon,
+        due: Vec<Id>,
+    ) -> eyre::Result<Vec<Tracked>> {
+        info!("polling {} simulation(s)...", due.len());
+
+        let polled = poll::fetch_all(&self.client, due.iter().cloned()).await?;
+
+        let mut store = daemon.store.lock().await;
+        let mut finished = vec![];
+        let now = Utc::now();
+
+        for (id, res) in due.iter().zip(polled) {
+            let Some(tracked) = store.get_mut(id) else {
+                continue;
+            };
+
+            let changed = match res {
+                Ok(sim) => {
+                    let changed = tracked.status() != Some(sim.status);
+                    tracked.simulation = Some(sim);
+                    tracked.last_error = None;
+
+                    changed
+                }
+                Err(err) if err.downcast_ref::<poll::NotFound>().is_some() => {
+                    tracked.not_found = true;
+                    tracked.last_error = Some(format!("{err:#}"));
+
+                    true
+                }
+                Err(err) => {
+                    warn!("{err:#}. will retry later.");
+                    tracked.last_error = Some(format!("{err:#}"));
+
+                    false
+                }
+            };
+
+            if changed && tracked.is_done() {
+                // NOTE: due right away for downloading results, if any.
+                tracked.last_polled_at = Some(now);
+                tracked.next_poll_at = now;
+                finished.push(tracked.clone());
+
+                continue;
+            }
+
+            let backoff = if changed {
+                self.min_interval
+            } else {
+                (Duration::from_secs(tracked.backoff_secs) * 2)
+                    .clamp(self.min_interval, self.max_interval)
+            };
+
+            tracked.backoff_secs = backoff.as_secs();
+            tracked.last_polled_at = Some(now);
+            tracked.next_poll_at = now + backoff;
+        }
+
+        store.save().await?;
+        daemon.updated.send_replace(());
+
+        Ok(finished)
+    }
+
+    async fn finished(&self, tracked: &Tracked) {
+        let name = tracked
+            .simulation
+            .as_ref()
+            .map_or_else(|| tracked.id.to_string(), |sim| sim.name.clone());
+
+        let url = tracked
+            .simulation
+            .as_ref()
+            .map(|sim| format!(": {}", sim.browser_url))
+            .unwrap_or_default();
+
+        self.notifiers
+            .send(&Notification {
+                title: match tracked.status() {
+                    Some(status) if !tracked.not_found => {
+                        format!("Simulation {status}")
+                    }
+                    _ => "Simulation not found".into(),
+                },
+                message: format!("`{name}` {}{url}", tracked.describe()),
+                simulations: tracked.simulation.iter().cloned().collect(),
+            })
+            .await;
+    }
+
+    /// Downloads the results of `sim`, backing off to retry later on failure.
+    async fn download(
+        &self,
+        daemon: &Daemon,
+        sim: &SimulationV7,
+    ) -> eyre::Result<()> {
+        let Some(download_dir) = &self.download_dir else {
+            return Ok(());
+        };
+
+        let res =
+            download_attachments(&self.http_client, sim, download_dir).await;
+
+        let mut store = daemon.store.lock().await;
+
+        let Some(tracked) = store.get_mut(&sim.id) else {
+            return Ok(());
+        };
+
+        match res {
+            Ok(dest) => {
+                info!(
+                    "downloaded results of `{}` to `{}`",
+                    sim.id,
+                    dest.display()
+                );
+
+                tracked.downloaded_to = Some(dest);
+                tracked.last_error = None;
+            }
+            Err(err) => {
+                warn!(
+                    "failed to download results of `{}`: {err:#}. will retry later.",
+                    sim.id
+                );
+
+                let backoff = (Duration::from_secs(tracked.backoff_secs) * 2)
+                    .clamp(self.min_interval, self.max_interval);
+
+                tracked.last_error = Some(format!("{err:#}"));
+                tracked.backoff_secs = backoff.as_secs();
+                tracked.next_poll_at = Utc::now() + backoff;
+            }
+        }
+
+        store.save().await?;
+        daemon.updated.send_replace(());
+
+        Ok(())
+    }
+}
+
+/// Downloads all attachments of `sim` in a folder of its own within `dir`.
+async fn download_attachments(
+    http_client: &reqwest::Client,
+    sim: &SimulationV7,
+    dir: &Path,
+) -> eyre::Result<PathBuf> {
+    let Some(results) = &sim.results else {
+        eyre::bail!("simulation has no results");
+    };
+
+    let dest = dir.join(format!("{}-{}", sanitize(&sim.name), sim.id));
+    fs::create_dir_all(&dest)
+        .await
+        .wrap_err_with(|| format!("creating `{}`", dest.display()))?;
+
+    let attachments = &results.attachments;
+
+    for (label, url) in [
+        ("report", &attachments.report_url),
+        ("spreadsheet", &attachments.spreadsheet_url),
+        ("raw-data", &attachments.raw_data_url),
+        ("slice-images", &attachments.slice_images_url),
+    ] {
+        let Some(url) = url else {
+            continue;
+        };
+
+        let url = reqwest::Url::parse(&url.0)?;
+        let filename = url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|segment| !segment.is_empty())
+            .map_or_else(|| label.to_owned(), sanitize);
+
+        download(http_client, url, &dest.join(filename))
+            .await
+            .wrap_err_with(|| format!("downloading {label}"))?;
+    }
+
+    Ok(dest)
+}
+
+async fn download(
+    http_client: &reqwest::Client,
+    url: reqwest::Url,
+    path: &Path,
+) -> eyre::Result<()> {
+    let mut stream = http_client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes_stream();
+
+    let mut file = fs::File::create(path)
+        .await
+        .wrap_err_with(|| format!("creating `{}`", path.display()))?;
+
+    while let Some(chunk) = stream.next().await {
+        file.write_all(&chunk?).await?;
+    }
+
+    file.flush().await?;
+
+    Ok(())
+}
+
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
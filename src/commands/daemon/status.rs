@@ -0,0 +1,92 @@
+use crate::{
+    aerocloud::{fmt::human_simulation_status, types::Id},
+    args::Args,
+    daemon::{self, Request, Response, store::Tracked},
+    fmt::{NOT_AVAILABLE, link},
+    utils::new_dynamic_table,
+};
+use chrono::Local;
+use color_eyre::eyre;
+
+pub async fn run(args: &Args, ids: &[Id]) -> eyre::Result<()> {
+    let Response::Simulations { simulations } =
+        daemon::request(args.daemon_addr, Request::Status { ids: ids.to_vec() })
+            .await?
+    else {
+        eyre::bail!("unexpected response from daemon");
+    };
+
+    print(args, &simulations)
+}
+
+pub fn print(args: &Args, simulations: &[Tracked]) -> eyre::Result<()> {
+    if let Some(template) = &args.template {
+        template.print_all(simulations)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string(simulations)?);
+    } else {
+        print_human(simulations);
+    }
+
+    Ok(())
+}
+
+fn print_human(simulations: &[Tracked]) {
+    if simulations.is_empty() {
+        println!("<empty>");
+        return;
+    }
+
+    let mut table = new_dynamic_table();
+    table.set_header(vec![
+        "Id",
+        "Name",
+        "Status",
+        "Last polled at",
+        "Next poll at",
+        "Downloaded to",
+        "Last error",
+        "",
+    ]);
+
+    for tracked in simulations {
+        table.add_row(vec![
+            tracked.id.to_string(),
+            tracked
+                .simulation
+                .as_ref()
+                .map_or(NOT_AVAILABLE, |sim| sim.name.as_str())
+                .into(),
+            if tracked.not_found {
+                "not found".into()
+            } else {
+                tracked
+                    .status()
+                    .map_or(NOT_AVAILABLE, human_simulation_status)
+                    .into()
+            },
+            tracked.last_polled_at.map_or_else(
+                || NOT_AVAILABLE.into(),
+                |at| at.with_timezone(&Local).to_string(),
+            ),
+            if tracked.is_done() {
+                NOT_AVAILABLE.into()
+            } else {
+                tracked.next_poll_at.with_timezone(&Local).to_string()
+            },
+            tracked
+                .downloaded_to
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            tracked.last_error.clone().unwrap_or_default(),
+            tracked
+                .simulation
+                .as_ref()
+                .map(|sim| link(&sim.browser_url))
+                .unwrap_or_default(),
+        ]);
+    }
+
+    println!("{table}");
+}
@@ -0,0 +1,19 @@
+use crate::{
+    aerocloud::types::Id,
+    args::Args,
+    daemon::{self, Request},
+};
+use color_eyre::eyre;
+
+pub async fn run(args: &Args, ids: &[Id]) -> eyre::Result<()> {
+    daemon::request(args.daemon_addr, Request::Track { ids: ids.to_vec() })
+        .await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string(&ids)?);
+    } else {
+        println!("Tracking {} simulation(s)", ids.len());
+    }
+
+    Ok(())
+}
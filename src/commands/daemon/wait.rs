@@ -0,0 +1,39 @@
+use crate::{
+    aerocloud::types::{Id, SimulationStatus},
+    args::Args,
+    daemon::{self, Request, Response},
+};
+use color_eyre::eyre::{self, WrapErr};
+use std::time::Duration;
+
+pub async fn run(
+    args: &Args,
+    ids: &[Id],
+    timeout: Option<Duration>,
+) -> eyre::Result<()> {
+    let request = Request::Wait { ids: ids.to_vec() };
+    let request = daemon::request(args.daemon_addr, request);
+
+    let response = if let Some(timeout) = timeout {
+        tokio::time::timeout(timeout, request)
+            .await
+            .wrap_err("timed out waiting for simulations")??
+    } else {
+        request.await?
+    };
+
+    let Response::Simulations { simulations } = response else {
+        eyre::bail!("unexpected response from daemon");
+    };
+
+    super::status::print(args, &simulations)?;
+
+    if simulations
+        .iter()
+        .any(|tracked| tracked.status() != Some(SimulationStatus::Success))
+    {
+        eyre::bail!("not all simulations succeeded");
+    }
+
+    Ok(())
+}
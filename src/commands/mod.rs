@@ -1,2 +1,5 @@
 pub mod aerocloud;
+pub mod cache;
 pub mod config;
+pub mod daemon;
+pub mod shell;
@@ -0,0 +1,270 @@
+use crate::{
+    aerocloud::{
+        Client,
+        cache::Cache,
+        types::{Id, ListPageProjectsV7, ProjectV7},
+    },
+    args::{AeroCloudV7Command, Args, ShellCommand, ShellLine, UseTarget},
+    commands::aerocloud::run_v7,
+    config::Config,
+    http,
+};
+use clap::{Command, CommandFactory, FromArgMatches, builder::Resettable};
+use color_eyre::eyre::{self, WrapErr};
+use rustyline::{
+    Context, Editor, Helper, completion::Completer, completion::Pair,
+    error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::FileHistory, validate::Validator,
+};
+use std::path::PathBuf;
+
+struct ShellHelper;
+
+pub async fn run(args: &Args, mut config: Config) -> eyre::Result<()> {
+    let client =
+        http::build_aerocloud_client_from_config(&config, &args.http_timeout())?;
+    let cache = Cache::new(args);
+
+    let mut editor: Editor<ShellHelper, FileHistory> = Editor::new()?;
+    editor.set_helper(Some(ShellHelper));
+
+    let history_path = history_path();
+
+    if let Some(path) = &history_path
+        && path.exists()
+    {
+        editor.load_history(path).wrap_err("loading history")?;
+    }
+
+    println!("Type `help` for the list of commands, `exit` or Ctrl-D to leave.");
+
+    let mut project: Option<ProjectV7> = None;
+
+    loop {
+        let prompt = project
+            .as_ref()
+            .map_or_else(|| "nf> ".into(), |p| format!("nf ({})> ", p.name));
+
+        // NOTE: completers block on a runtime of their own, which can't be done
+        // from within this one.
+        let (returned_editor, line) = tokio::task::spawn_blocking(move || {
+            let line = editor.readline(&prompt);
+            (editor, line)
+        })
+        .await?;
+        editor = returned_editor;
+
+        let line = match line {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        editor.add_history_entry(&line)?;
+
+        let words = match shell_words::split(&line) {
+            Ok(words) => words,
+            Err(err) => {
+                eprintln!("error: {err}");
+                continue;
+            }
+        };
+
+        let parsed = command(config.project.project_id.is_some())
+            .try_get_matches_from(words)
+            .and_then(|matches| ShellLine::from_arg_matches(&matches));
+
+        let command = match parsed {
+            Ok(ShellLine { command }) => command,
+            Err(err) => {
+                err.print()?;
+                continue;
+            }
+        };
+
+        match command {
+            ShellCommand::Exit => break,
+            ShellCommand::Use {
+                target: UseTarget::Project { project: query },
+            } => match find_project(&client, &cache, &query).await {
+                Ok(found) => {
+                    println!("Using project `{}` ({})", found.name, found.id);
+                    config.project.project_id = Some(found.id.clone());
+                    project = Some(found);
+                }
+                Err(err) => eprintln!("Error: {err:#}"),
+            },
+            ShellCommand::V7(mut command) => {
+                if let Some(project_id) = &config.project.project_id {
+                    default_project(&mut command, project_id);
+                }
+
+                tokio::select! {
+                    res = run_v7(args, &config, &client, &cache, &command) => {
+                        if let Err(err) = res {
+                            eprintln!("Error: {err:#}");
+                        }
+                    }
+                    _ = tokio::signal::ctrl_c() => {
+                        eprintln!("Interrupted");
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(path) = &history_path {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        editor.save_history(path).wrap_err("saving history")?;
+    }
+
+    Ok(())
+}
+
+fn history_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("nablaflow").join("shell_history"))
+}
+
+/// Builds the parser for a line. When a project is in use, ids become optional
+/// where the project can be used to discover simulations instead.
+fn command(has_project: bool) -> Command {
+    let mut cmd = ShellLine::command();
+
+    if !has_project {
+        return cmd;
+    }
+
+    let discovering: Vec<String> = cmd
+        .get_subcommands()
+        .filter(|sub| {
+            let has =
+                |id: &str| sub.get_arguments().any(|arg| arg.get_id() == id);
+
+            has("ids") && has("project")
+        })
+        .map(|sub| sub.get_name().to_owned())
+        .collect();
+
+    for name in discovering {
+        cmd = cmd.mut_subcommand(&name, |sub| {
+            sub.mut_arg("ids", |arg| {
+                arg.required_unless_present(Resettable::Reset)
+            })
+        });
+    }
+
+    cmd
+}
+
+/// Discovers simulations of the project in use when no ids are given to
+/// commands which support it.
+fn default_project(command: &mut AeroCloudV7Command, project_id: &Id) {
+    if let AeroCloudV7Command::WaitForSimulations { ids, project, .. }
+    | AeroCloudV7Command::Watch { ids, project, .. } = command
+        && ids.is_empty()
+        && project.is_none()
+    {
+        *project = Some(project_id.clone());
+    }
+}
+
+async fn find_project(
+    client: &Client,
+    cache: &Cache,
+    query: &str,
+) -> eyre::Result<ProjectV7> {
+    let projects = cache
+        .projects(|offset| async move {
+            let ListPageProjectsV7 { items, nav } = client
+                .projects_v7_list(None, Some(&offset), None)
+                .await?
+                .into_inner();
+
+            Ok((items, nav))
+        })
+        .await?;
+
+    let mut matching: Vec<ProjectV7> = projects
+        .into_iter()
+        .filter(|p| p.id.to_string() == query || p.name == query)
+        .collect();
+
+    match matching.len() {
+        0 => eyre::bail!("no project with name or id `{query}`"),
+        1 => Ok(matching.remove(0)),
+        n => eyre::bail!("{n} projects are named `{query}`, use its id instead"),
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+
+        let Ok(mut words) = shell_words::split(line) else {
+            return Ok((pos, vec![]));
+        };
+
+        let start = line.rfind(char::is_whitespace).map_or(0, |idx| idx + 1);
+
+        if start == pos {
+            words.push(String::new());
+        }
+
+        // NOTE: the engine expects the binary name first, even for multicall
+        // commands.
+        let arg_index = words.len();
+        let candidates = clap_complete::engine::complete(
+            &mut ShellLine::command(),
+            std::iter::once("nf".into())
+                .chain(words.into_iter().map(Into::into))
+                .collect(),
+            arg_index,
+            None,
+        )
+        .unwrap_or_default();
+
+        Ok((
+            start,
+            candidates
+                .into_iter()
+                .map(|candidate| {
+                    let value =
+                        candidate.get_value().to_string_lossy().into_owned();
+
+                    Pair {
+                        display: match candidate.get_help() {
+                            Some(help) => format!("{value}  ({help})"),
+                            None => value.clone(),
+                        },
+                        replacement: value,
+                    }
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
@@ -0,0 +1,118 @@
+use crate::{
+    aerocloud::{
+        Client,
+        cache::Cache,
+        types::{ListPageModelsV7, ListPageProjectsV7},
+    },
+    args::Args,
+    config::Config,
+    http,
+};
+use clap::Parser;
+use clap_complete::engine::CompletionCandidate;
+use color_eyre::eyre::{self, OptionExt};
+use std::time::Duration;
+
+/// How long to wait for the API when nothing is cached yet, so that pressing tab
+/// never hangs.
+const API_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn project_ids() -> Vec<CompletionCandidate> {
+    complete(&async |cache, client| {
+        let projects = cache
+            .projects(|offset| async move {
+                let ListPageProjectsV7 { items, nav } = client
+                    .ok_or_eyre("no token")?
+                    .projects_v7_list(None, Some(&offset), None)
+                    .await?
+                    .into_inner();
+
+                Ok((items, nav))
+            })
+            .await?;
+
+        Ok(projects
+            .into_iter()
+            .map(|project| candidate(project.id.to_string(), project.name))
+            .collect())
+    })
+}
+
+pub fn model_ids() -> Vec<CompletionCandidate> {
+    complete(&async |cache, client| {
+        let models = cache
+            .reusable_models(|offset| async move {
+                let ListPageModelsV7 { items, nav } = client
+                    .ok_or_eyre("no token")?
+                    .models_v7_list_reusable(None, Some(&offset))
+                    .await?
+                    .into_inner();
+
+                Ok((items, nav))
+            })
+            .await?;
+
+        Ok(models
+            .into_iter()
+            .map(|model| candidate(model.id.to_string(), model.name))
+            .collect())
+    })
+}
+
+/// Simulations can only be listed per project, so only cached ones are suggested.
+pub fn simulation_ids() -> Vec<CompletionCandidate> {
+    complete(&async |cache, _client| {
+        Ok(cache
+            .all_simulations()
+            .await?
+            .into_iter()
+            .map(|sim| candidate(sim.id.to_string(), sim.name))
+            .collect())
+    })
+}
+
+fn candidate(id: String, name: String) -> CompletionCandidate {
+    CompletionCandidate::new(id).help(Some(name.into()))
+}
+
+/// Runs `f` against the cache first and, when that fails, once more against the
+/// API with a short timeout. Errors are swallowed, as there is no way to show them
+/// while completing.
+fn complete<F>(f: &F) -> Vec<CompletionCandidate>
+where
+    F: AsyncFn(Cache, Option<&Client>) -> eyre::Result<Vec<CompletionCandidate>>,
+{
+    // NOTE: the command line being completed is incomplete by definition, so
+    // global options are taken from the ENV and their defaults only.
+    let Ok(args) = Args::try_parse_from(["nf", "cache", "stats"]) else {
+        return vec![];
+    };
+
+    let Ok(rt) = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    else {
+        return vec![];
+    };
+
+    rt.block_on(async {
+        let cache = Cache::new(&args);
+
+        if let Ok(candidates) = f(cache.clone().with_offline(true), None).await {
+            return candidates;
+        }
+
+        let client = Config::load(&args).await.and_then(|config| {
+            http::build_aerocloud_client_from_config(&config, &API_TIMEOUT)
+        });
+
+        tokio::time::timeout(
+            API_TIMEOUT,
+            f(cache.with_offline(false), client.as_ref().ok()),
+        )
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .unwrap_or_default()
+    })
+}
@@ -1,17 +1,25 @@
-use crate::args::Args;
+use crate::{aerocloud::types::Id, args::Args, notify::Notifier};
 use color_eyre::eyre::{self, WrapErr};
 use reqwest::Url;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 use tokio::fs as tokio_fs;
 
+pub mod project;
+
+use project::ProjectConfig;
+
 pub const DEFAULT_HOSTNAME: &str = "https://api.nablaflow.io";
 
 pub type Token = String;
 
+/// A token along with the hostname it is sent to.
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
-pub struct Config {
-    #[serde(skip_serializing_if = "Option::is_none")]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub aerocloud_token: Option<String>,
 
     #[serde(
@@ -23,6 +31,36 @@ pub struct Config {
     pub hostname: Option<Url>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Config {
+    /// Used when no profile is selected.
+    #[serde(flatten)]
+    pub default_profile: Profile,
+
+    /// Selected with `--profile` or `profile` in a project config.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub profiles: BTreeMap<String, Profile>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub notifiers: Vec<Notifier>,
+
+    /// Hostnames project configs may point to, as the token is sent to them.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub trusted_hostnames: Vec<String>,
+
+    /// Found by walking up from the current dir, never written back.
+    #[serde(skip)]
+    pub project: ProjectConfig,
+
+    /// Name of the selected profile, if any.
+    #[serde(skip)]
+    pub profile: Option<String>,
+
+    /// Token and hostname passed as flags or env, never written back.
+    #[serde(skip)]
+    overrides: Profile,
+}
+
 impl Config {
     pub fn default_path() -> Option<PathBuf> {
         Some(dirs::config_dir()?.join("nablaflow").join("config.json"))
@@ -31,9 +69,21 @@ impl Config {
     pub async fn load(args: &Args) -> eyre::Result<Self> {
         let mut config = Self::load_from_path(&args.config_path).await?;
 
-        config.aerocloud_token =
-            args.aerocloud_auth_token.clone().or(config.aerocloud_token);
-        config.hostname = args.hostname.clone().or(config.hostname);
+        config.overrides = Profile {
+            aerocloud_token: args.aerocloud_auth_token.clone(),
+            hostname: args.hostname.clone(),
+        };
+
+        config.project = ProjectConfig::discover().await?.unwrap_or_default();
+        config.profile = args
+            .profile
+            .clone()
+            .or_else(|| config.project.profile.clone());
+
+        // NOTE: flags and env take precedence over the project config.
+        if args.hostname.is_some() {
+            config.project.hostname = None;
+        }
 
         Ok(config)
     }
@@ -51,14 +101,105 @@ impl Config {
         tokio_fs::write(path, &buf).await.wrap_err("writing config")
     }
 
+    /// The selected profile, `None` when it is missing from the config.
+    fn active_profile(&self) -> Option<&Profile> {
+        match &self.profile {
+            Some(name) => self.profiles.get(name),
+            None => Some(&self.default_profile),
+        }
+    }
+
+    /// The selected profile, created when missing, to make changes to.
+    pub fn active_profile_mut(&mut self) -> &mut Profile {
+        match &self.profile {
+            Some(name) => self.profiles.entry(name.clone()).or_default(),
+            None => &mut self.default_profile,
+        }
+    }
+
+    /// Sets the token of the selected profile, also using it over the one
+    /// passed as flag or env.
+    pub fn set_aerocloud_token(&mut self, token: Token) {
+        self.active_profile_mut().aerocloud_token = Some(token);
+        self.overrides.aerocloud_token = None;
+    }
+
     pub fn aerocloud_token_or_fail(&self) -> eyre::Result<&Token> {
-        self.aerocloud_token
-            .as_ref()
-            .ok_or_else(|| eyre::eyre!("No token provided. Either call `nf aerocloud set-auth-token` or pass it from the ENV. See `nf aerocloud --help` for more."))
+        if let Some(token) = &self.overrides.aerocloud_token {
+            return Ok(token);
+        }
+
+        let token = self
+            .active_profile()
+            .and_then(|profile| profile.aerocloud_token.as_ref());
+
+        match (token, &self.profile) {
+            (Some(token), _) => Ok(token),
+            (None, Some(name)) => Err(eyre::eyre!(
+                "No token provided for profile `{name}`. Either call `nf --profile {name} aerocloud set-auth-token` or pass it from the ENV. See `nf aerocloud --help` for more."
+            )),
+            (None, None) => Err(eyre::eyre!(
+                "No token provided. Either call `nf aerocloud set-auth-token` or pass it from the ENV. See `nf aerocloud --help` for more."
+            )),
+        }
+    }
+
+    /// Fails when the project config points to a hostname other than the one in
+    /// the user config which has not been trusted, not to leak the token to it.
+    pub fn check_hostname(&self) -> eyre::Result<()> {
+        let Some(hostname) = &self.project.hostname else {
+            return Ok(());
+        };
+
+        let user_hostname = self.profile_hostname();
+
+        if *hostname == user_hostname
+            || self
+                .trusted_hostnames
+                .iter()
+                .any(|h| h == hostname.as_str())
+        {
+            return Ok(());
+        }
+
+        eyre::bail!(
+            "`{}` points to `{hostname}` instead of `{user_hostname}`, your AeroCloud token would be sent to it. If you trust it, run `nf config trust-hostname {hostname}` or pass `--hostname {hostname}`.",
+            self.project
+                .path
+                .as_deref()
+                .unwrap_or_else(|| Path::new(project::FILENAME))
+                .display()
+        )
     }
 
     pub fn hostname(&self) -> Url {
-        self.hostname.clone().unwrap_or_else(default_hostname)
+        self.project
+            .hostname
+            .clone()
+            .unwrap_or_else(|| self.profile_hostname())
+    }
+
+    /// The hostname passed as flag or env, otherwise the one of the selected
+    /// profile.
+    fn profile_hostname(&self) -> Url {
+        self.overrides
+            .hostname
+            .clone()
+            .or_else(|| {
+                self.active_profile()
+                    .and_then(|profile| profile.hostname.clone())
+            })
+            .unwrap_or_else(default_hostname)
+    }
+
+    /// The given project id, otherwise the one from the project config.
+    pub fn project_id_or_fail<'a>(
+        &'a self,
+        project_id: Option<&'a Id>,
+    ) -> eyre::Result<&'a Id> {
+        project_id
+            .or(self.project.project_id.as_ref())
+            .ok_or_else(|| eyre::eyre!("No project id provided. Either pass it as argument or set `project_id` in a `{}` file.", project::FILENAME))
     }
 
     async fn load_from_path(path: &Path) -> eyre::Result<Self> {
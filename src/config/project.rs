@@ -0,0 +1,141 @@
+use crate::{aerocloud::types::Id, params_file::ParamsFormat};
+use color_eyre::eyre::{self, WrapErr};
+use reqwest::Url;
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use tokio::fs as tokio_fs;
+
+pub const FILENAME: &str = ".nablaflow.toml";
+
+/// Per-directory config, usually committed along with the study it applies to.
+///
+/// ```toml
+/// hostname = "https://api.nablaflow.io"
+/// profile = "work"
+/// project_id = "..."
+/// batch_root = "simulations"
+/// git_revision = "{branch}@{hash}{dirty}"
+///
+/// [simulation]
+/// quality = "basic"
+/// fluid_speed = 30.0
+/// ```
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Where the file was found, if any.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+
+    #[serde(deserialize_with = "super::deserialize_url", default)]
+    pub hostname: Option<Url>,
+
+    /// Profile in the user config to take the token and hostname from.
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    #[serde(default)]
+    pub project_id: Option<Id>,
+
+    /// Relative to the folder containing the file.
+    #[serde(default)]
+    pub batch_root: Option<PathBuf>,
+
+    /// Format for filling `revision` from git, see `--git-revision`.
+    #[serde(default)]
+    pub git_revision: Option<String>,
+
+    #[serde(default)]
+    pub simulation: SimulationDefaults,
+}
+
+impl ProjectConfig {
+    /// Looks for the file in the current folder and all of its parents, the
+    /// closest one wins.
+    pub async fn discover() -> eyre::Result<Option<Self>> {
+        let cwd = std::env::current_dir().wrap_err("reading current dir")?;
+
+        for dir in cwd.ancestors() {
+            let path = dir.join(FILENAME);
+
+            if path.is_file() {
+                return Self::load_from_path(&path).await.map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn load_from_path(path: &Path) -> eyre::Result<Self> {
+        let buf = tokio_fs::read_to_string(path)
+            .await
+            .wrap_err_with(|| format!("reading {}", path.display()))?;
+
+        let mut config: Self = toml::from_str(&buf)
+            .wrap_err_with(|| format!("parsing {}", path.display()))?;
+
+        if let Some(dir) = path.parent()
+            && let Some(batch_root) = &config.batch_root
+        {
+            config.batch_root = Some(dir.join(batch_root));
+        }
+
+        config.path = Some(path.to_owned());
+
+        Ok(config)
+    }
+}
+
+/// Simulation params used for whatever is not set in a params file.
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct SimulationDefaults(pub Map<String, Value>);
+
+impl SimulationDefaults {
+    #[must_use]
+    pub fn with(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.0.entry(key).or_insert_with(|| value.into());
+        self
+    }
+
+    /// Parses params, falling back to defaults for missing keys.
+    pub fn parse<T: DeserializeOwned>(
+        &self,
+        format: ParamsFormat,
+        buf: &[u8],
+    ) -> eyre::Result<T> {
+        // NOTE: parse straight into `T` when possible, so that errors keep their
+        // position in the file.
+        if self.0.is_empty() {
+            return format.parse(buf);
+        }
+
+        let mut value: Value = format.parse(buf)?;
+
+        if let Value::Object(params) = &mut value {
+            merge(params, &self.0);
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Builds params out of defaults alone.
+    pub fn build<T: DeserializeOwned>(&self) -> eyre::Result<T> {
+        Ok(serde_json::from_value(Value::Object(self.0.clone()))?)
+    }
+}
+
+/// Adds keys from `defaults` missing in `params`, recursing into objects.
+pub fn merge(params: &mut Map<String, Value>, defaults: &Map<String, Value>) {
+    for (key, default) in defaults {
+        match (params.get_mut(key), default) {
+            (None, _) => {
+                params.insert(key.clone(), default.clone());
+            }
+            (Some(Value::Object(params)), Value::Object(defaults)) => {
+                merge(params, defaults);
+            }
+            (Some(_), _) => {}
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{aerocloud::types::Id, daemon::store::Tracked};
+use color_eyre::eyre::{self, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
+use tokio::{
+    fs,
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+
+pub mod store;
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:47011";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A request to the daemon, sent as a single JSON line.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// Starts tracking the given simulations.
+    Track { ids: Vec<Id> },
+    /// Returns the given tracked simulations, all of them when empty.
+    Status { ids: Vec<Id> },
+    /// Tracks the given simulations and responds once all of them are done.
+    Wait { ids: Vec<Id> },
+}
+
+/// A [`Request`] along with the token proving the client may read the file the
+/// daemon wrote it to, i.e. that it runs as the same user.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Envelope {
+    pub token: String,
+    #[serde(flatten)]
+    pub request: Request,
+}
+
+/// The response to a [`Request`], sent as a single JSON line.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Ok,
+    Simulations { simulations: Vec<Tracked> },
+    Error { message: String },
+}
+
+/// Sends `request` to the daemon listening on `addr` and waits for its response.
+pub async fn request(
+    addr: SocketAddr,
+    request: Request,
+) -> eyre::Result<Response> {
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(addr))
+        .await
+        .map_err(eyre::Report::from)
+        .and_then(|res| res.map_err(eyre::Report::from))
+        .wrap_err_with(|| {
+            format!(
+                "connecting to daemon at `{addr}`, is it running? Start it with `nf daemon run`"
+            )
+        })?;
+
+    let (read, mut write) = stream.into_split();
+
+    let envelope = Envelope {
+        token: read_token().await?,
+        request,
+    };
+
+    let mut buf = serde_json::to_vec(&envelope)?;
+    buf.push(b'\n');
+    write.write_all(&buf).await?;
+
+    let line = BufReader::new(read)
+        .lines()
+        .next_line()
+        .await?
+        .ok_or_else(|| eyre::eyre!("daemon closed the connection"))?;
+
+    match serde_json::from_str(&line).wrap_err("parsing daemon response")? {
+        Response::Error { message } => eyre::bail!("daemon: {message}"),
+        response => Ok(response),
+    }
+}
+
+/// Asks the daemon to track `ids`, doing nothing when it is not running.
+pub async fn track_if_running(addr: SocketAddr, ids: Vec<Id>) {
+    if let Err(err) = request(addr, Request::Track { ids }).await {
+        tracing::debug!("not tracking simulations in daemon: {err:#}");
+    }
+}
+
+/// File the token clients authenticate with is written to by the daemon, in the
+/// runtime dir where available.
+pub fn token_path() -> eyre::Result<PathBuf> {
+    dirs::runtime_dir()
+        .or_else(dirs::data_dir)
+        .map(|dir| dir.join("nablaflow").join("daemon.token"))
+        .ok_or_else(|| eyre::eyre!("failed to determine runtime dir"))
+}
+
+/// Generates a new token and writes it to [`token_path`], readable only by the
+/// current user.
+pub async fn write_token() -> eyre::Result<String> {
+    let path = token_path()?;
+    let token = uuid::Uuid::new_v4().simple().to_string();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .wrap_err_with(|| format!("creating `{}`", parent.display()))?;
+    }
+
+    // NOTE: removed first as permissions are only set when creating the file.
+    match fs::remove_file(&path).await {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(err)
+                .wrap_err_with(|| format!("removing `{}`", path.display()));
+        }
+        _ => {}
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options
+        .open(&path)
+        .await
+        .wrap_err_with(|| format!("creating `{}`", path.display()))?;
+
+    file.write_all(token.as_bytes()).await?;
+    file.flush().await?;
+
+    Ok(token)
+}
+
+async fn read_token() -> eyre::Result<String> {
+    let path = token_path()?;
+
+    let token = fs::read_to_string(&path).await.wrap_err_with(|| {
+        format!(
+            "reading daemon token from `{}`, is it running? Start it with `nf daemon run`",
+            path.display()
+        )
+    })?;
+
+    Ok(token.trim().to_owned())
+}
@@ -0,0 +1,169 @@
+use crate::aerocloud::types::{Id, SimulationStatus, SimulationV7};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{self, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// A simulation tracked by the daemon along with its polling state.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Tracked {
+    pub id: Id,
+    pub added_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simulation: Option<SimulationV7>,
+    /// Set once the API reports the simulation does not exist.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub not_found: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_polled_at: Option<DateTime<Utc>>,
+    pub next_poll_at: DateTime<Utc>,
+    #[serde(default)]
+    pub backoff_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downloaded_to: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl Tracked {
+    pub fn new(id: Id) -> Self {
+        let now = Utc::now();
+
+        Self {
+            id,
+            added_at: now,
+            simulation: None,
+            not_found: false,
+            last_polled_at: None,
+            next_poll_at: now,
+            backoff_secs: 0,
+            downloaded_to: None,
+            last_error: None,
+        }
+    }
+
+    pub fn status(&self) -> Option<SimulationStatus> {
+        self.simulation.as_ref().map(|sim| sim.status)
+    }
+
+    /// Whether the simulation reached a status it will not leave anymore, so that
+    /// polling can stop. Drafts count as such, as tracked simulations are the
+    /// submitted ones and a draft is not going to run unless submitted again.
+    pub fn is_done(&self) -> bool {
+        self.not_found
+            || matches!(
+                self.status(),
+                Some(
+                    SimulationStatus::Success
+                        | SimulationStatus::Expired
+                        | SimulationStatus::Draft
+                )
+            )
+    }
+
+    /// Whether the simulation succeeded but its results were not downloaded yet.
+    pub fn needs_download(&self) -> bool {
+        self.status() == Some(SimulationStatus::Success)
+            && self.downloaded_to.is_none()
+    }
+
+    /// How the simulation is doing, for humans.
+    pub fn describe(&self) -> &'static str {
+        if self.not_found {
+            return "was not found";
+        }
+
+        match self.status() {
+            Some(SimulationStatus::Success) => "has completed",
+            Some(SimulationStatus::Expired) => "has expired",
+            Some(SimulationStatus::Draft) => "has not been submitted",
+            Some(SimulationStatus::Progress) => "is in progress",
+            None => "has not been polled yet",
+        }
+    }
+}
+
+/// Simulations tracked by the daemon, persisted as JSON.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Store {
+    #[serde(skip)]
+    path: PathBuf,
+
+    pub simulations: Vec<Tracked>,
+}
+
+impl Store {
+    pub fn default_path() -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join("nablaflow").join("daemon.json"))
+    }
+
+    pub async fn load(path: &Path) -> eyre::Result<Self> {
+        let mut store: Self = if path.exists() {
+            let buf = fs::read(path)
+                .await
+                .wrap_err_with(|| format!("reading {}", path.display()))?;
+
+            serde_json::from_slice(&buf).wrap_err("parsing store as json")?
+        } else {
+            Self::default()
+        };
+
+        path.clone_into(&mut store.path);
+
+        Ok(store)
+    }
+
+    /// Writes the store to a temporary file first, so that it is never left
+    /// half-written.
+    pub async fn save(&self) -> eyre::Result<()> {
+        let buf = serde_json::to_vec_pretty(self)
+            .wrap_err("serializing store to json")?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .wrap_err("creating parent folders for store")?;
+        }
+
+        let tmp_path = self.path.with_extension("json.tmp");
+
+        fs::write(&tmp_path, &buf)
+            .await
+            .wrap_err_with(|| format!("writing {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &self.path)
+            .await
+            .wrap_err_with(|| format!("writing {}", self.path.display()))
+    }
+
+    /// Starts tracking `ids`, returning how many were not tracked yet.
+    pub fn track(&mut self, ids: &[Id]) -> usize {
+        let mut added = 0;
+
+        for id in ids {
+            if self.get(id).is_none() {
+                self.simulations.push(Tracked::new(id.clone()));
+                added += 1;
+            }
+        }
+
+        added
+    }
+
+    pub fn get(&self, id: &Id) -> Option<&Tracked> {
+        self.simulations.iter().find(|t| t.id.0 == id.0)
+    }
+
+    pub fn get_mut(&mut self, id: &Id) -> Option<&mut Tracked> {
+        self.simulations.iter_mut().find(|t| t.id.0 == id.0)
+    }
+
+    /// Returns the given simulations in order, all of them when `ids` is empty.
+    pub fn select(&self, ids: &[Id]) -> Vec<Tracked> {
+        if ids.is_empty() {
+            self.simulations.clone()
+        } else {
+            ids.iter().filter_map(|id| self.get(id)).cloned().collect()
+        }
+    }
+}
@@ -0,0 +1,492 @@
+use crate::utils::parse_datetime;
+use chrono::{DateTime, Utc};
+use color_eyre::eyre;
+use serde::Serialize;
+use serde_json::Value;
+use std::{cmp::Ordering, fmt, iter::Peekable, str::FromStr, vec::IntoIter};
+
+/// A client-side filter expression evaluated against the JSON representation of
+/// an API object, e.g. `revision ~ "v3*" && created_at > 2026-09-01`.
+///
+/// Fields are addressed with dotted paths (`params.quality`), arrays can be indexed
+/// (`results.yaw_angles[0].cd`) or traversed without an index, in which case the
+/// comparison holds when any of the elements satisfies it. Coefficients can be
+/// addressed straight from `results` (`results.cd`), standing for those at any
+/// yaw angle.
+#[derive(Clone, Debug)]
+pub struct Filter {
+    source: String,
+    expr: Expr,
+}
+
+impl Filter {
+    pub fn matches(&self, value: &Value) -> bool {
+        self.expr.eval(value)
+    }
+
+    pub fn retain_matching<T: Serialize>(
+        &self,
+        items: Vec<T>,
+    ) -> eyre::Result<Vec<T>> {
+        let mut retained = Vec::with_capacity(items.len());
+
+        for item in items {
+            if self.matches(&serde_json::to_value(&item)?) {
+                retained.push(item);
+            }
+        }
+
+        Ok(retained)
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        let mut tokens = tokenize(s)?.into_iter().peekable();
+
+        let expr = parse_or(&mut tokens)?;
+
+        if let Some(token) = tokens.next() {
+            eyre::bail!("unexpected {token} in filter expression");
+        }
+
+        Ok(Self {
+            source: s.to_owned(),
+            expr,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Exists(Path),
+    Compare(Path, CmpOp, Literal),
+}
+
+impl Expr {
+    fn eval(&self, value: &Value) -> bool {
+        match self {
+            Self::And(lhs, rhs) => lhs.eval(value) && rhs.eval(value),
+            Self::Or(lhs, rhs) => lhs.eval(value) || rhs.eval(value),
+            Self::Not(expr) => !expr.eval(value),
+            Self::Exists(path) => path
+                .resolve(value)
+                .into_iter()
+                .any(|v| !matches!(v, Value::Null | Value::Bool(false))),
+            Self::Compare(path, op, literal) => path
+                .resolve(value)
+                .into_iter()
+                .any(|v| op.holds(v, literal)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CmpOp {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Glob,
+}
+
+impl fmt::Display for CmpOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Eq => "==",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Glob => "~",
+        })
+    }
+}
+
+impl CmpOp {
+    fn holds(self, value: &Value, literal: &Literal) -> bool {
+        if self == Self::Glob {
+            return match value {
+                Value::String(s) => glob_match(&literal.as_pattern(), s),
+                Value::Number(n) => {
+                    glob_match(&literal.as_pattern(), &n.to_string())
+                }
+                _ => false,
+            };
+        }
+
+        let Some(ordering) = literal.compare(value) else {
+            return false;
+        };
+
+        match self {
+            Self::Eq => ordering == Ordering::Equal,
+            Self::Lt => ordering == Ordering::Less,
+            Self::Le => ordering != Ordering::Greater,
+            Self::Gt => ordering == Ordering::Greater,
+            Self::Ge => ordering != Ordering::Less,
+            Self::Glob => unreachable!(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    DateTime(DateTime<Utc>),
+    String(String),
+}
+
+impl Literal {
+    fn from_word(word: String) -> Self {
+        match word.as_str() {
+            "null" => return Self::Null,
+            "true" => return Self::Bool(true),
+            "false" => return Self::Bool(false),
+            _ => {}
+        }
+
+        if let Ok(n) = word.parse::<f64>() {
+            Self::Number(n)
+        } else if let Ok(dt) = parse_datetime(&word) {
+            Self::DateTime(dt)
+        } else {
+            Self::String(word)
+        }
+    }
+
+    fn as_pattern(&self) -> String {
+        match self {
+            Self::Null => "null".into(),
+            Self::Bool(b) => b.to_string(),
+            Self::Number(n) => n.to_string(),
+            Self::DateTime(dt) => dt.to_rfc3339(),
+            Self::String(s) => s.clone(),
+        }
+    }
+
+    /// Orders `value` relative to this literal, `None` when they are not comparable.
+    fn compare(&self, value: &Value) -> Option<Ordering> {
+        match (self, value) {
+            (Self::Null, Value::Null) => Some(Ordering::Equal),
+            (Self::Bool(b), Value::Bool(v)) => Some(v.cmp(b)),
+            (Self::Number(n), Value::Number(v)) => v.as_f64()?.partial_cmp(n),
+            (Self::DateTime(dt), Value::String(v)) => {
+                Some(parse_datetime(v).ok()?.cmp(dt))
+            }
+            (Self::String(s), Value::String(v)) => Some(v.as_str().cmp(s)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Path(Vec<Segment>);
+
+#[derive(Clone, Debug)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+impl Path {
+    fn parse(s: &str) -> eyre::Result<Self> {
+        let mut segments = vec![];
+
+        for part in s.split('.') {
+            let (key, mut rest) =
+                part.split_once('[').map_or((part, ""), |(k, r)| (k, r));
+
+            if key.is_empty()
+                || !key.chars().all(|c| c.is_alphanumeric() || c == '_')
+            {
+                eyre::bail!("invalid field `{s}` in filter expression");
+            }
+
+            segments.push(Segment::Key(key.into()));
+
+            while !rest.is_empty() {
+                let Some((idx, tail)) = rest.split_once(']') else {
+                    eyre::bail!("unclosed `[` in field `{s}`");
+                };
+
+                segments.push(Segment::Index(idx.parse().map_err(|_| {
+                    eyre::eyre!("invalid index `{idx}` in field `{s}`")
+                })?));
+
+                rest = tail.strip_prefix('[').unwrap_or(tail);
+
+                if !tail.is_empty() && !tail.starts_with('[') {
+                    eyre::bail!("unexpected `{tail}` in field `{s}`");
+                }
+            }
+        }
+
+        // NOTE: results hold coefficients per yaw angle, `results.cd` is a
+        // shorthand for `results.yaw_angles.cd`.
+        if let [Segment::Key(first), Segment::Key(second), ..] = &segments[..]
+            && first == "results"
+            && !matches!(second.as_str(), "yaw_angles" | "attachments")
+        {
+            segments.insert(1, Segment::Key("yaw_angles".into()));
+        }
+
+        Ok(Self(segments))
+    }
+
+    fn resolve<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut curr = vec![value];
+
+        for segment in &self.0 {
+            curr = curr
+                .into_iter()
+                .flat_map(|v| match (segment, v) {
+                    (Segment::Key(key), Value::Object(map)) => {
+                        map.get(key).into_iter().collect()
+                    }
+                    (Segment::Key(key), Value::Array(items)) => {
+                        items.iter().filter_map(|item| item.get(key)).collect()
+                    }
+                    (Segment::Index(idx), Value::Array(items)) => {
+                        items.get(*idx).into_iter().collect()
+                    }
+                    _ => vec![],
+                })
+                .collect();
+        }
+
+        curr.into_iter()
+            .flat_map(|v| match v {
+                Value::Array(items) => items.iter().collect(),
+                v => vec![v],
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Op(CmpOp),
+    Quoted(String),
+    Word(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LParen => f.write_str("`(`"),
+            Self::RParen => f.write_str("`)`"),
+            Self::And => f.write_str("`&&`"),
+            Self::Or => f.write_str("`||`"),
+            Self::Not => f.write_str("`!`"),
+            Self::Op(op) => write!(f, "operator `{op}`"),
+            Self::Quoted(s) => write!(f, "string \"{s}\""),
+            Self::Word(s) => write!(f, "`{s}`"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> eyre::Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '~' => Token::Op(CmpOp::Glob),
+            '&' | '|' => {
+                if chars.next_if_eq(&c).is_none() {
+                    eyre::bail!("expected `{c}{c}` in filter expression");
+                }
+
+                if c == '&' { Token::And } else { Token::Or }
+            }
+            '=' => {
+                chars.next_if_eq(&'=');
+                Token::Op(CmpOp::Eq)
+            }
+            '!' => {
+                // `!=` and `!~` are negations of `==` and `~`, so that they hold
+                // for missing fields and when no element of an array matches.
+                if let Some(op) = chars.next_if(|ch| *ch == '=' || *ch == '~') {
+                    tokens.push(Token::Not);
+                    Token::Op(if op == '=' { CmpOp::Eq } else { CmpOp::Glob })
+                } else {
+                    Token::Not
+                }
+            }
+            '<' | '>' => {
+                let or_equal = chars.next_if_eq(&'=').is_some();
+
+                Token::Op(match (c, or_equal) {
+                    ('<', false) => CmpOp::Lt,
+                    ('<', true) => CmpOp::Le,
+                    ('>', false) => CmpOp::Gt,
+                    _ => CmpOp::Ge,
+                })
+            }
+            '"' | '\'' => {
+                let mut buf = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            buf.extend(chars.next());
+                        }
+                        Some(ch) if ch == c => break,
+                        Some(ch) => buf.push(ch),
+                        None => {
+                            eyre::bail!(
+                                "unterminated string in filter expression"
+                            )
+                        }
+                    }
+                }
+
+                Token::Quoted(buf)
+            }
+            c => {
+                let mut buf = String::from(c);
+
+                while let Some(ch) = chars.next_if(|ch| {
+                    !ch.is_whitespace() && !"()~&|=!<>\"'".contains(*ch)
+                }) {
+                    buf.push(ch);
+                }
+
+                Token::Word(buf)
+            }
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+type Tokens = Peekable<IntoIter<Token>>;
+
+fn parse_or(tokens: &mut Tokens) -> eyre::Result<Expr> {
+    let mut expr = parse_and(tokens)?;
+
+    while tokens.next_if_eq(&Token::Or).is_some() {
+        expr = Expr::Or(Box::new(expr), Box::new(parse_and(tokens)?));
+    }
+
+    Ok(expr)
+}
+
+fn parse_and(tokens: &mut Tokens) -> eyre::Result<Expr> {
+    let mut expr = parse_unary(tokens)?;
+
+    while tokens.next_if_eq(&Token::And).is_some() {
+        expr = Expr::And(Box::new(expr), Box::new(parse_unary(tokens)?));
+    }
+
+    Ok(expr)
+}
+
+fn parse_unary(tokens: &mut Tokens) -> eyre::Result<Expr> {
+    match tokens.next() {
+        Some(Token::Not) => Ok(Expr::Not(Box::new(parse_unary(tokens)?))),
+        Some(Token::LParen) => {
+            let expr = parse_or(tokens)?;
+
+            if tokens.next() != Some(Token::RParen) {
+                eyre::bail!("expected `)` in filter expression");
+            }
+
+            Ok(expr)
+        }
+        Some(Token::Word(field)) => {
+            let path = Path::parse(&field)?;
+
+            let negated = tokens.next_if_eq(&Token::Not).is_some();
+
+            let Some(Token::Op(op)) = tokens.peek().cloned() else {
+                if negated {
+                    eyre::bail!(
+                        "expected `=` or `~` after `!` in filter expression"
+                    );
+                }
+
+                return Ok(Expr::Exists(path));
+            };
+            tokens.next();
+
+            let literal = match tokens.next() {
+                Some(Token::Quoted(s)) => Literal::String(s),
+                Some(Token::Word(w)) => Literal::from_word(w),
+                Some(token) => {
+                    eyre::bail!("expected a value after `{field}`, found {token}")
+                }
+                None => eyre::bail!("expected a value after `{field}`"),
+            };
+
+            let expr = Expr::Compare(path, op, literal);
+
+            Ok(if negated {
+                Expr::Not(Box::new(expr))
+            } else {
+                expr
+            })
+        }
+        Some(token) => eyre::bail!("unexpected {token} in filter expression"),
+        None => eyre::bail!("unexpected end of filter expression"),
+    }
+}
+
+/// Matches `s` against a pattern where `*` stands for any sequence of characters and
+/// `?` for a single one.
+pub fn glob_match(pattern: &str, s: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+
+    let (mut p, mut i) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while i < s.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, i));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == s[i] => {
+                p += 1;
+                i += 1;
+            }
+            _ => {
+                let Some((star_p, star_i)) = backtrack else {
+                    return false;
+                };
+
+                p = star_p + 1;
+                i = star_i + 1;
+                backtrack = Some((star_p, star_i + 1));
+            }
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
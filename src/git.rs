@@ -0,0 +1,117 @@
+use color_eyre::eyre::{self, WrapErr};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+use tokio::process::Command;
+
+/// Used when `--git-revision` is passed without a format.
+pub const DEFAULT_REVISION_FORMAT: &str = "{branch}@{hash}{dirty}";
+
+/// Placeholders understood by [`Commit::format`].
+pub const REVISION_PLACEHOLDERS: &str = "`{hash}`, `{branch}`, `{tag}` and `{dirty}` (`-dirty` with uncommitted changes in the folder of the params)";
+
+/// The checked out commit of a git repository.
+#[derive(Debug, Clone)]
+pub struct Commit {
+    pub hash: String,
+    /// `None` when the head is detached.
+    pub branch: Option<String>,
+    /// A tag pointing exactly at the commit.
+    pub tag: Option<String>,
+    pub dirty: bool,
+}
+
+impl Commit {
+    /// Reads the commit checked out in the repository containing `dir`, `None`
+    /// when there's no such repository or it has no commits yet. Only changes
+    /// within `dir` or to `files` make it dirty.
+    pub async fn discover(
+        dir: &Path,
+        files: &[PathBuf],
+    ) -> eyre::Result<Option<Self>> {
+        let Some(hash) = git(dir, &["rev-parse", "--short", "HEAD"]).await?
+        else {
+            return Ok(None);
+        };
+
+        let branch = git(dir, &["rev-parse", "--abbrev-ref", "HEAD"])
+            .await?
+            .filter(|branch| branch != "HEAD");
+        let tag =
+            git(dir, &["describe", "--tags", "--exact-match", "HEAD"]).await?;
+        // NOTE: git takes relative paths as relative to `dir`.
+        let files = files
+            .iter()
+            .map(std::path::absolute)
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err("resolving paths of params")?;
+
+        let mut status = vec![
+            OsStr::new("status"),
+            OsStr::new("--porcelain"),
+            OsStr::new("--"),
+            OsStr::new("."),
+        ];
+        status.extend(files.iter().map(|file| file.as_os_str()));
+
+        let dirty = git(dir, &status)
+            .await?
+            .is_some_and(|status| !status.is_empty());
+
+        Ok(Some(Self {
+            hash,
+            branch,
+            tag,
+            dirty,
+        }))
+    }
+
+    /// Expands placeholders in `format`, missing values become empty.
+    pub fn format(&self, format: &str) -> String {
+        format
+            .replace("{hash}", &self.hash)
+            .replace("{branch}", self.branch.as_deref().unwrap_or_default())
+            .replace("{tag}", self.tag.as_deref().unwrap_or_default())
+            .replace("{dirty}", if self.dirty { "-dirty" } else { "" })
+    }
+}
+
+/// Revision for params read from `dir` and `files` around it, formatted from
+/// its git repository.
+pub async fn revision(
+    dir: &Path,
+    files: &[PathBuf],
+    format: &str,
+) -> eyre::Result<String> {
+    let commit = Commit::discover(dir, files).await?.ok_or_else(|| {
+        eyre::eyre!(
+            "`{}` is not in a git repository with commits",
+            dir.display()
+        )
+    })?;
+
+    Ok(commit.format(format))
+}
+
+/// Runs git in `dir`, returning its trimmed output or `None` if it failed.
+async fn git<S: AsRef<OsStr>>(
+    dir: &Path,
+    args: &[S],
+) -> eyre::Result<Option<String>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .await
+        .wrap_err("running git, is it installed?")?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_owned(),
+    ))
+}
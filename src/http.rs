@@ -15,6 +15,8 @@ pub fn build_aerocloud_client_from_config(
     config: &Config,
     timeout: &Duration,
 ) -> eyre::Result<aerocloud::Client> {
+    config.check_hostname()?;
+
     let base_url = config.hostname().join("/aerocloud")?;
     let http_client =
         build_http_client(config.aerocloud_token_or_fail()?, timeout)?;
@@ -25,6 +27,17 @@ pub fn build_aerocloud_client_from_config(
     ))
 }
 
+/// Builds a client for requests to third parties, which must not carry the token.
+pub fn build_http_client_without_token(
+    timeout: &Duration,
+) -> eyre::Result<Client> {
+    reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(*timeout)
+        .build()
+        .wrap_err("building http client")
+}
+
 fn build_http_client(token: &Token, timeout: &Duration) -> eyre::Result<Client> {
     let mut headers = header::HeaderMap::new();
 
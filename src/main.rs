@@ -1,5 +1,6 @@
-use crate::{args::Args, config::Config};
+use crate::{args::Args, config::Config, utils::ExitCode};
 use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use color_eyre::eyre::{self, WrapErr};
 use std::io;
 use update_informer::{Check, registry};
@@ -7,14 +8,39 @@ use update_informer::{Check, registry};
 mod aerocloud;
 mod args;
 mod commands;
+mod completion;
 mod config;
+mod daemon;
+mod filter;
 mod fmt;
+mod git;
 mod http;
+mod notify;
+mod params_file;
+mod template;
 mod tracing;
 mod utils;
 
-#[tokio::main(flavor = "current_thread")]
-async fn main() -> eyre::Result<()> {
+fn main() -> eyre::Result<()> {
+    // NOTE: must run before the runtime is started, since completers block on a
+    // runtime of their own.
+    CompleteEnv::with_factory(Args::command).complete();
+
+    let res = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(run());
+
+    if let Err(err) = &res
+        && let Some(ExitCode(code)) = err.downcast_ref()
+    {
+        std::process::exit(*code);
+    }
+
+    res
+}
+
+async fn run() -> eyre::Result<()> {
     color_eyre::install()?;
 
     let args = Args::parse();
@@ -34,6 +60,15 @@ async fn main() -> eyre::Result<()> {
         args::Scope::AeroCloud { ref command } => {
             commands::aerocloud::run(&args, config, command).await?;
         }
+        args::Scope::Shell => {
+            commands::shell::run(&args, config).await?;
+        }
+        args::Scope::Cache { ref command } => {
+            commands::cache::run(&args, command).await?;
+        }
+        args::Scope::Daemon { ref command } => {
+            commands::daemon::run(&args, config, command).await?;
+        }
         args::Scope::GenerateCompletions { shell } => {
             let mut args = Args::command();
             let name = args.get_name().to_string();
@@ -0,0 +1,196 @@
+use crate::{aerocloud::types::SimulationV7, args::Args, config::Config, http};
+use color_eyre::eyre::{self, WrapErr, bail};
+use minijinja::Environment;
+use serde::{Deserialize, Serialize};
+use std::{fmt, process::Stdio, str::FromStr};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+/// Payload posted to webhooks when none is configured, understood by both Slack and
+/// Teams incoming webhooks.
+const DEFAULT_WEBHOOK_PAYLOAD: &str =
+    r#"{"text": {{ (title ~ ": " ~ message) }}}"#;
+
+/// A sink notifications are delivered to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Notifier {
+    /// POSTs a JSON payload, rendered from a template with the notification as
+    /// context (`title`, `message` and `simulations`), to `url`.
+    Webhook {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<String>,
+    },
+    /// Runs `command` with a JSON array of the simulations on stdin, the title and
+    /// message in `NF_NOTIFICATION_TITLE` and `NF_NOTIFICATION_MESSAGE`. Its
+    /// output is discarded.
+    Command { command: String },
+    /// Shows a desktop notification.
+    Desktop,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Notification {
+    pub title: String,
+    pub message: String,
+    /// Simulations the notification is about.
+    pub simulations: Vec<SimulationV7>,
+}
+
+/// Notifiers in effect for an invocation along with what is needed to deliver to
+/// them.
+#[derive(Debug, Clone)]
+pub struct Notifiers {
+    notifiers: Vec<Notifier>,
+    http_client: reqwest::Client,
+}
+
+impl FromStr for Notifier {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        if s == "desktop" {
+            return Ok(Self::Desktop);
+        }
+
+        match s.split_once(':') {
+            Some(("webhook", url)) => {
+                reqwest::Url::parse(url)
+                    .wrap_err_with(|| format!("parsing webhook url `{url}`"))?;
+
+                Ok(Self::Webhook {
+                    url: url.to_owned(),
+                    payload: None,
+                })
+            }
+            Some(("command", command)) => {
+                shell_words::split(command)
+                    .wrap_err_with(|| format!("parsing command `{command}`"))?;
+
+                Ok(Self::Command {
+                    command: command.to_owned(),
+                })
+            }
+            _ => bail!(
+                "expected `desktop`, `webhook:<URL>` or `command:<COMMAND>`, got `{s}`"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Notifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Webhook { url, .. } => write!(f, "webhook `{url}`"),
+            Self::Command { command } => write!(f, "command `{command}`"),
+            Self::Desktop => write!(f, "desktop"),
+        }
+    }
+}
+
+impl Notifier {
+    async fn send(
+        &self,
+        http_client: &reqwest::Client,
+        notification: &Notification,
+    ) -> eyre::Result<()> {
+        match self {
+            Self::Webhook { url, payload } => {
+                let mut env = Environment::new();
+
+                // NOTE: the `.json` suffix makes values render as JSON.
+                env.add_template(
+                    "payload.json",
+                    payload.as_deref().unwrap_or(DEFAULT_WEBHOOK_PAYLOAD),
+                )
+                .wrap_err("parsing webhook payload template")?;
+
+                let body = env
+                    .get_template("payload.json")?
+                    .render(notification)
+                    .wrap_err("rendering webhook payload")?;
+
+                http_client
+                    .post(url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+            Self::Command { command } => {
+                let words = shell_words::split(command)?;
+                let Some((program, args)) = words.split_first() else {
+                    bail!("empty command");
+                };
+
+                let mut child = Command::new(program)
+                    .args(args)
+                    .env("NF_NOTIFICATION_TITLE", &notification.title)
+                    .env("NF_NOTIFICATION_MESSAGE", &notification.message)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()?;
+
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin
+                        .write_all(&serde_json::to_vec(
+                            &notification.simulations,
+                        )?)
+                        .await?;
+                }
+
+                let status = child.wait().await?;
+
+                if !status.success() {
+                    bail!("exited with {status}");
+                }
+            }
+            Self::Desktop => {
+                let notification = notification.clone();
+
+                tokio::task::spawn_blocking(move || {
+                    notify_rust::Notification::new()
+                        .appname("nf")
+                        .summary(&notification.title)
+                        .body(&notification.message)
+                        .show()
+                        .map(|_| ())
+                })
+                .await??;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Notifiers {
+    /// Notifiers given on the command line take precedence over those in the config.
+    pub fn new(args: &Args, config: &Config) -> eyre::Result<Self> {
+        let notifiers = if args.notify.is_empty() {
+            config.notifiers.clone()
+        } else {
+            args.notify.clone()
+        };
+
+        Ok(Self {
+            notifiers,
+            http_client: http::build_http_client_without_token(
+                &args.http_timeout(),
+            )?,
+        })
+    }
+
+    /// Delivers to all notifiers, logging failures instead of returning them so that
+    /// a broken sink does not fail the command.
+    pub async fn send(&self, notification: &Notification) {
+        for notifier in &self.notifiers {
+            if let Err(err) = notifier.send(&self.http_client, notification).await
+            {
+                tracing::warn!("failed to notify {notifier}: {err:#}");
+            }
+        }
+    }
+}
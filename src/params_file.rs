@@ -0,0 +1,294 @@
+use crate::aerocloud::schema::{self, Definition, Violation};
+use color_eyre::eyre;
+use itertools::Itertools;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, IgnoredAny, MapAccess,
+    SeqAccess, Visitor,
+};
+use serde_json::Value;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// Stands for the path of params read from stdin.
+pub const STDIN: &str = "<stdin>";
+
+/// Formats params can be written in, picked by file extension.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParamsFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ParamsFormat {
+    pub const ALL: [Self; 3] = [Self::Json, Self::Yaml, Self::Toml];
+
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Json => &["json"],
+            Self::Yaml => &["yaml", "yml"],
+            Self::Toml => &["toml"],
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+    }
+
+    /// Looks for `base` with any of the extensions appended, e.g. `params.json`
+    /// or `params.yaml` for `dir/params`, `front.wing.json` for `dir/front.wing`.
+    /// Having more than one is an error, as it would be unclear which one applies.
+    pub fn find(base: &Path) -> eyre::Result<Option<PathBuf>> {
+        let mut found = Self::ALL
+            .iter()
+            .flat_map(|format| format.extensions())
+            .map(|extension| {
+                let mut path = base.as_os_str().to_owned();
+                path.push(".");
+                path.push(extension);
+                PathBuf::from(path)
+            })
+            .filter(|path| path.is_file());
+
+        let Some(path) = found.next() else {
+            return Ok(None);
+        };
+
+        if let Some(other) = found.next() {
+            eyre::bail!(
+                "both `{}` and `{}` found, keep only one of them",
+                path.display(),
+                other.display()
+            );
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Parses `buf`, errors point to the line and column at fault.
+    pub fn parse<T: DeserializeOwned>(self, buf: &[u8]) -> eyre::Result<T> {
+        Ok(match self {
+            Self::Json => serde_json::from_slice(buf)?,
+            Self::Yaml => serde_norway::from_slice(buf)?,
+            Self::Toml => toml::from_slice(buf)?,
+        })
+    }
+
+    /// Checks `value`, parsed out of `buf` read from `path`, against
+    /// `definition`, pointing each problem to where it is in `buf`.
+    pub fn validate(
+        self,
+        path: &Path,
+        buf: &[u8],
+        value: &Value,
+        definition: Definition,
+    ) -> Vec<Problem> {
+        self.locate_violations(path, buf, schema::validate(value, definition))
+    }
+
+    /// Turns `violations` of params read from `path` into problems pointing to
+    /// where they are in `buf`.
+    pub fn locate_violations(
+        self,
+        path: &Path,
+        buf: &[u8],
+        violations: Vec<Violation>,
+    ) -> Vec<Problem> {
+        let src = String::from_utf8_lossy(buf);
+
+        violations
+            .into_iter()
+            .map(|violation| Problem {
+                file: path.display().to_string(),
+                position: self.locate(&src, &violation.pointer),
+                violation,
+            })
+            .collect()
+    }
+
+    /// 1-based line and column of the value at `pointer`, or of the closest
+    /// parent one found, as reported by the parser itself.
+    fn locate(self, src: &str, pointer: &str) -> Option<(usize, usize)> {
+        let tokens: Vec<String> = pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect();
+
+        (1..=tokens.len())
+            .rev()
+            .find_map(|len| self.seek(src, &tokens[..len]))
+    }
+
+    /// Parses `src` down to the value at `tokens`, failing there so that the
+    /// error carries its position.
+    fn seek(self, src: &str, tokens: &[String]) -> Option<(usize, usize)> {
+        let seek = Seek { tokens };
+
+        match self {
+            Self::Json => {
+                let mut de = serde_json::Deserializer::from_str(src);
+                let err = seek.deserialize(&mut de).err()?;
+
+                is_sought(&err).then(|| (err.line(), err.column()))
+            }
+            Self::Yaml => {
+                let err = seek
+                    .deserialize(serde_norway::Deserializer::from_str(src))
+                    .err()?;
+
+                is_sought(&err)
+                    .then(|| err.location())
+                    .flatten()
+                    .map(|location| (location.line(), location.column()))
+            }
+            Self::Toml => {
+                let de = toml::de::Deserializer::parse(src).ok()?;
+                let err = seek.deserialize(de).err()?;
+
+                is_sought(&err)
+                    .then(|| err.span())
+                    .flatten()
+                    .map(|span| line_column(src, span.start))
+            }
+        }
+    }
+}
+
+/// Message of the error raised once the sought value is reached, set apart from
+/// any other by a control character.
+const SOUGHT: &str = "\u{1}sought";
+
+fn is_sought(err: &impl fmt::Display) -> bool {
+    err.to_string().contains(SOUGHT)
+}
+
+fn line_column(src: &str, offset: usize) -> (usize, usize) {
+    let before = &src[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |line| line.chars().count())
+        + 1;
+
+    (line, column)
+}
+
+/// Walks a document along JSON pointer `tokens`, skipping everything else, and
+/// fails on the value they lead to.
+struct Seek<'a> {
+    tokens: &'a [String],
+}
+
+impl<'de> DeserializeSeed<'de> for Seek<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<(), D::Error> {
+        de.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Seek<'_> {
+    type Value = ();
+
+    // NOTE: scalars are rejected as unexpected, with this in the message when
+    // they're the sought value.
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.tokens.is_empty() {
+            f.write_str(SOUGHT)
+        } else {
+            f.write_str("an object or an array")
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let Some((key, tokens)) = self.tokens.split_first() else {
+            return Err(de::Error::custom(SOUGHT));
+        };
+
+        while let Some(next) = map.next_key::<String>()? {
+            if next == *key {
+                return map.next_value_seed(Seek { tokens });
+            }
+
+            map.next_value::<IgnoredAny>()?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let Some((idx, tokens)) = self.tokens.split_first() else {
+            return Err(de::Error::custom(SOUGHT));
+        };
+
+        let Ok(idx) = idx.parse::<usize>() else {
+            return Ok(());
+        };
+
+        for _ in 0..idx {
+            if seq.next_element::<IgnoredAny>()?.is_none() {
+                return Ok(());
+            }
+        }
+
+        seq.next_element_seed(Seek { tokens })?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ParamsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+        })
+    }
+}
+
+/// A problem found in a params file.
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub file: String,
+    pub position: Option<(usize, usize)>,
+    pub violation: Violation,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.file)?;
+
+        if let Some((line, column)) = self.position {
+            write!(f, ":{line}:{column}")?;
+        }
+
+        write!(f, ": {}", self.violation)
+    }
+}
+
+/// Fails listing all `problems`, if any.
+pub fn ensure_no_problems(problems: &[Problem]) -> eyre::Result<()> {
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    eyre::bail!(
+        "found {} problem(s) in params:\n{}",
+        problems.len(),
+        problems
+            .iter()
+            .sorted_by(|a, b| (&a.file, a.position).cmp(&(&b.file, b.position)))
+            .map(|problem| format!("  {problem}"))
+            .join("\n")
+    );
+}
@@ -0,0 +1,119 @@
+use color_eyre::eyre::{self, WrapErr};
+use itertools::Itertools;
+use minijinja::Environment;
+use serde::Serialize;
+use std::{fs, path::Path};
+
+/// Templates shipped in the binary, selectable by name.
+pub const BUILTIN: &[(&str, &str)] = &[
+    ("oneline", include_str!("templates/oneline.j2")),
+    ("markdown", include_str!("templates/markdown.j2")),
+    ("slack", include_str!("templates/slack.j2")),
+    ("results", include_str!("templates/results.j2")),
+];
+
+/// A user-provided template rendered once per object returned by a command, with the
+/// serialized object as context, e.g. `{{name}} rev {{revision}}`.
+#[derive(Debug, Clone)]
+pub struct Template {
+    source: String,
+}
+
+impl Template {
+    const NAME: &str = "template";
+
+    /// Extensions that make a spec be taken as a path to a template file.
+    const FILE_EXTENSIONS: &[&str] = &["j2", "jinja", "html", "md", "txt"];
+
+    /// Resolves `spec` as a built-in template name, a path to a template file or
+    /// otherwise as the template itself.
+    ///
+    /// Specs looking like a path (with a separator or a template extension, and
+    /// no template tags) must point to an existing file, so that a mistyped path
+    /// isn't printed as is.
+    pub fn resolve(spec: &str) -> eyre::Result<Self> {
+        if let Some((_, source)) = BUILTIN.iter().find(|(name, _)| *name == spec)
+        {
+            return Ok(Self {
+                source: (*source).to_owned(),
+            });
+        }
+
+        let path = Path::new(spec);
+
+        let source = if path.is_file() {
+            fs::read_to_string(path).wrap_err_with(|| {
+                format!("reading template from `{}`", path.display())
+            })?
+        } else if Self::looks_like_path(spec) {
+            eyre::bail!("template file `{}` not found", path.display());
+        } else {
+            spec.to_owned()
+        };
+
+        let template = Self { source };
+        template.compile(&mut Environment::new())?;
+
+        Ok(template)
+    }
+
+    fn looks_like_path(spec: &str) -> bool {
+        if spec.contains("{{") || spec.contains("{%") {
+            return false;
+        }
+
+        spec.contains(std::path::MAIN_SEPARATOR)
+            || spec.contains('/')
+            || Path::new(spec)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| Self::FILE_EXTENSIONS.contains(&ext))
+    }
+
+    pub fn builtin_names() -> String {
+        BUILTIN
+            .iter()
+            .map(|(name, _)| format!("`{name}`"))
+            .join(", ")
+    }
+
+    /// Renders each item on its own line, skipping those which produce no output.
+    pub fn render_all<T: Serialize>(&self, items: &[T]) -> eyre::Result<String> {
+        let mut env = Environment::new();
+        self.compile(&mut env)?;
+        let template = env.get_template(Self::NAME)?;
+
+        let mut lines = vec![];
+
+        for item in items {
+            let rendered =
+                template.render(item).wrap_err("rendering template")?;
+            let rendered = rendered.trim_end_matches('\n');
+
+            if !rendered.is_empty() {
+                lines.push(rendered.to_owned());
+            }
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    pub fn print_all<T: Serialize>(&self, items: &[T]) -> eyre::Result<()> {
+        let rendered = self.render_all(items)?;
+
+        if !rendered.is_empty() {
+            println!("{rendered}");
+        }
+
+        Ok(())
+    }
+
+    pub fn print<T: Serialize>(&self, item: &T) -> eyre::Result<()> {
+        self.print_all(std::slice::from_ref(item))
+    }
+
+    fn compile<'a>(&'a self, env: &mut Environment<'a>) -> eyre::Result<()> {
+        env.add_template(Self::NAME, &self.source)
+            .wrap_err("parsing template")
+    }
+}
@@ -1,6 +1,22 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use color_eyre::eyre;
 use comfy_table::{
     ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
 };
+use std::fmt;
+
+/// Returned by commands which need the process to exit with a specific code,
+/// leaving it to the caller to actually exit.
+#[derive(Debug)]
+pub struct ExitCode(pub i32);
+
+impl fmt::Display for ExitCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exited with code {}", self.0)
+    }
+}
+
+impl std::error::Error for ExitCode {}
 
 pub fn new_dynamic_table() -> Table {
     let mut t = Table::new();
@@ -10,3 +26,33 @@ pub fn new_dynamic_table() -> Table {
         .apply_modifier(UTF8_ROUND_CORNERS);
     t
 }
+
+/// Parses a RFC3339 timestamp or a `YYYY-MM-DD` date, taken as midnight UTC.
+pub fn parse_datetime(s: &str) -> eyre::Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "`{s}` is neither a RFC3339 timestamp nor a YYYY-MM-DD date"
+            )
+        })
+}
+
+/// Parses a fluid speed in m/s, which must be positive.
+pub fn parse_speed(s: &str) -> eyre::Result<f64> {
+    let speed: f64 = s
+        .parse()
+        .map_err(|_| eyre::eyre!("`{s}` is not a number"))?;
+
+    if !speed.is_finite() || speed <= 0.0 {
+        eyre::bail!("speed must be greater than 0 m/s");
+    }
+
+    Ok(speed)
+}